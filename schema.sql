@@ -10,6 +10,7 @@ CREATE TABLE blocks
     blockhash String,
     previous_blockhash String,
     block_time DateTime,
+    vote_transactions UInt32 DEFAULT 0,
     insertion_time DateTime MATERIALIZED now(),
 )
 ENGINE = MergeTree
@@ -33,6 +34,11 @@ CREATE TABLE transactions
     signer6 String DEFAULT '',
     signer7 String DEFAULT '',
     -- signers Array(String),
+    compute_unit_limit UInt32 DEFAULT 0,
+    compute_unit_price UInt64 DEFAULT 0,
+    priority_fee_micro_lamports UInt64 DEFAULT 0,
+    version LowCardinality(String) DEFAULT 'legacy',
+    lookup_tables Array(String),
     PROJECTION projection_signature (SELECT * ORDER BY signature) -- RECOMMENDED
 )
 ENGINE = MergeTree
@@ -40,61 +46,1117 @@ PARTITION BY toInt64(slot / 1e6)
 PRIMARY KEY (slot, transaction_index)
 ORDER BY (slot, transaction_index);
 
+CREATE TABLE transaction_lookup_tables
+(
+    slot UInt64,
+    transaction_index UInt64,
+    lookup_table_index UInt64,
+    lookup_table LowCardinality(String) CODEC(LZ4),
+    writable_indexes Array(UInt8),
+    readonly_indexes Array(UInt8),
+    writable_addresses Array(String),
+    readonly_addresses Array(String),
+    PROJECTION projection_lookup_table (SELECT * ORDER BY lookup_table, slot, transaction_index, lookup_table_index), -- RECOMMENDED
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, lookup_table_index)
+ORDER BY (slot, transaction_index, lookup_table_index);
+
+-- VOTES
+
+CREATE TABLE vote_counts
+(
+    slot UInt64,
+    vote_account LowCardinality(String) CODEC(LZ4),
+    votes UInt32,
+    PROJECTION projection_vote_account (SELECT * ORDER BY vote_account, slot), -- RECOMMENDED
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, vote_account)
+ORDER BY (slot, vote_account);
+
+CREATE TABLE vote_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    "type" String,
+    vote_account LowCardinality(String) CODEC(LZ4),
+    vote_authority LowCardinality(String) CODEC(LZ4),
+    voted_slots Array(UInt64),
+    last_voted_slot Nullable(UInt64),
+    root_slot Nullable(UInt64),
+    timestamp Nullable(DateTime),
+    PROJECTION projection_vote_account (SELECT * ORDER BY vote_account, slot, transaction_index, instruction_index), -- RECOMMENDED
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+-- TOKEN HOLDER BALANCES
+
+CREATE TABLE token_holder_balances
+(
+    account String,
+    owner String,
+    mint String,
+    balance UInt64,
+    slot UInt64,
+    PROJECTION projection_owner (SELECT * ORDER BY owner, mint), -- RECOMMENDED
+    PROJECTION projection_mint (SELECT * ORDER BY mint, owner) -- RECOMMENDED
+)
+ENGINE = ReplacingMergeTree(slot)
+PRIMARY KEY (account)
+ORDER BY (account);
+
+-- MINTS
+
+CREATE TABLE mints
+(
+    mint String,
+    decimals Nullable(UInt8),
+    supply Nullable(Int128),
+    mint_authority LowCardinality(String) CODEC(LZ4),
+    freeze_authority LowCardinality(String) CODEC(LZ4),
+    name String,
+    symbol String,
+    uri String,
+    creation_slot Nullable(UInt64),
+    slot UInt64,
+    PROJECTION projection_symbol (SELECT * ORDER BY symbol, mint) -- RECOMMENDED
+)
+ENGINE = ReplacingMergeTree(slot)
+PRIMARY KEY mint
+ORDER BY mint;
+
+-- DEX TRADES
+
+CREATE TABLE dex_trades
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    trade_index UInt32,
+    partial_signature String,
+    partial_blockhash String,
+    protocol LowCardinality(String) CODEC(LZ4),
+    pool LowCardinality(String) CODEC(LZ4),
+    trader LowCardinality(String) CODEC(LZ4),
+    signer LowCardinality(String) CODEC(LZ4),
+    base_mint LowCardinality(String) CODEC(LZ4),
+    quote_mint LowCardinality(String) CODEC(LZ4),
+    base_amount UInt64,
+    quote_amount UInt64,
+    side LowCardinality(String) CODEC(LZ4),
+    price Nullable(Float64),
+    usd_value Nullable(Float64),
+    PROJECTION projection_pool (SELECT * ORDER BY pool, slot, transaction_index, instruction_index, trade_index), -- RECOMMENDED
+    PROJECTION projection_trader (SELECT * ORDER BY trader, slot, transaction_index, instruction_index, trade_index), -- RECOMMENDED
+    PROJECTION projection_base_mint (SELECT * ORDER BY base_mint, slot, transaction_index, instruction_index, trade_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index, trade_index)
+ORDER BY (slot, transaction_index, instruction_index, trade_index);
+
+-- CANDLES
+
+CREATE TABLE candles_1m
+(
+    market_type LowCardinality(String) CODEC(LZ4),
+    market String,
+    quote_mint LowCardinality(String) CODEC(LZ4),
+    timestamp DateTime,
+    open Float64,
+    high Float64,
+    low Float64,
+    close Float64,
+    base_volume Float64,
+    quote_volume Float64,
+    is_closed Boolean,
+    slot UInt64,
+)
+ENGINE = ReplacingMergeTree(slot)
+PRIMARY KEY (market_type, market, quote_mint, timestamp)
+ORDER BY (market_type, market, quote_mint, timestamp);
+
+CREATE TABLE candles_1h
+(
+    market_type LowCardinality(String) CODEC(LZ4),
+    market String,
+    quote_mint LowCardinality(String) CODEC(LZ4),
+    timestamp DateTime,
+    open Float64,
+    high Float64,
+    low Float64,
+    close Float64,
+    base_volume Float64,
+    quote_volume Float64,
+    is_closed Boolean,
+    slot UInt64,
+)
+ENGINE = ReplacingMergeTree(slot)
+PRIMARY KEY (market_type, market, quote_mint, timestamp)
+ORDER BY (market_type, market, quote_mint, timestamp);
+
+CREATE TABLE candles_1d
+(
+    market_type LowCardinality(String) CODEC(LZ4),
+    market String,
+    quote_mint LowCardinality(String) CODEC(LZ4),
+    timestamp DateTime,
+    open Float64,
+    high Float64,
+    low Float64,
+    close Float64,
+    base_volume Float64,
+    quote_volume Float64,
+    is_closed Boolean,
+    slot UInt64,
+)
+ENGINE = ReplacingMergeTree(slot)
+PRIMARY KEY (market_type, market, quote_mint, timestamp)
+ORDER BY (market_type, market, quote_mint, timestamp);
+
 -- RAYDIUM AMM EVENTS
 
-CREATE TABLE raydium_amm_swap_events
+CREATE TABLE raydium_pools
+(
+    amm String,
+    coin_mint LowCardinality(String) CODEC(LZ4),
+    pc_mint LowCardinality(String) CODEC(LZ4),
+    lp_mint LowCardinality(String) CODEC(LZ4),
+    coin_vault LowCardinality(String) CODEC(LZ4),
+    pc_vault LowCardinality(String) CODEC(LZ4),
+    coin_symbol String,
+    pc_symbol String,
+    creator LowCardinality(String) CODEC(LZ4),
+    open_slot Nullable(UInt64),
+    first_seen_slot Nullable(UInt64),
+    slot UInt64,
+    PROJECTION projection_coin_mint (SELECT * ORDER BY coin_mint, amm), -- RECOMMENDED
+    PROJECTION projection_pc_mint (SELECT * ORDER BY pc_mint, amm), -- RECOMMENDED
+)
+ENGINE = ReplacingMergeTree(slot)
+PRIMARY KEY amm
+ORDER BY amm;
+
+CREATE TABLE raydium_pool_states
+(
+    amm String,
+    slot UInt64,
+    pc_amount UInt64,
+    coin_amount UInt64,
+    lp_supply Nullable(Int128),
+    price Nullable(Float64),
+    tvl_usd Nullable(Float64),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (amm, slot)
+ORDER BY (amm, slot);
+
+CREATE TABLE raydium_amm_swap_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    amm LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    amount_in UInt64,
+    amount_out UInt64,
+    ui_amount_in Nullable(Decimal(38, 18)),
+    ui_amount_out Nullable(Decimal(38, 18)),
+    usd_value Nullable(Float64),
+    mint_in LowCardinality(String) CODEC(LZ4),
+    mint_out LowCardinality(String) CODEC(LZ4),
+    direction LowCardinality(String) CODEC(LZ4),
+    pool_pc_amount UInt64,
+    pool_coin_amount UInt64,
+    user_pre_balance_in UInt64,
+    user_pre_balance_out UInt64,
+    swap_type LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    minimum_amount_out Nullable(UInt64),
+    max_amount_in Nullable(UInt64),
+    coin_symbol String DEFAULT '',
+    pc_symbol String DEFAULT '',
+    pool_age_slots Nullable(UInt64),
+    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_in (SELECT * ORDER BY mint_in, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_out (SELECT * ORDER BY mint_out, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_amm_initialize_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    amm LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    pc_init_amount UInt64,
+    coin_init_amount UInt64,
+    lp_init_amount UInt64,
+    pc_mint LowCardinality(String) CODEC(LZ4),
+    coin_mint LowCardinality(String) CODEC(LZ4),
+    lp_mint LowCardinality(String) CODEC(LZ4),
+    user_pc_pre_balance UInt64,
+    user_coin_pre_balance UInt64,
+    open_time Nullable(UInt64),
+    market_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    market String DEFAULT '',
+    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_market (SELECT * ORDER BY market, slot, transaction_index, instruction_index),
+    PROJECTION projection_pc_mint (SELECT * ORDER BY pc_mint, slot, transaction_index, instruction_index),
+    PROJECTION projection_coin_mint (SELECT * ORDER BY coin_mint, slot, transaction_index, instruction_index),
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_amm_deposit_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    amm LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    pc_amount UInt64,
+    coin_amount UInt64,
+    pool_pc_amount UInt64,
+    pool_coin_amount UInt64,
+    lp_amount UInt64,
+    pc_mint LowCardinality(String) CODEC(LZ4),
+    coin_mint LowCardinality(String) CODEC(LZ4),
+    lp_mint LowCardinality(String) CODEC(LZ4),
+    user_pc_pre_balance UInt64,
+    user_coin_pre_balance UInt64,
+    coin_symbol String DEFAULT '',
+    pc_symbol String DEFAULT '',
+    pool_age_slots Nullable(UInt64),
+    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_pc_mint (SELECT * ORDER BY pc_mint, slot, transaction_index, instruction_index),
+    PROJECTION projection_coin_mint (SELECT * ORDER BY coin_mint, slot, transaction_index, instruction_index),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_amm_withdraw_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    amm LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    pc_amount UInt64,
+    coin_amount UInt64,
+    lp_amount UInt64,
+    pool_pc_amount UInt64,
+    pool_coin_amount UInt64,
+    pc_mint LowCardinality(String) CODEC(LZ4),
+    coin_mint LowCardinality(String) CODEC(LZ4),
+    lp_mint LowCardinality(String) CODEC(LZ4),
+    user_pc_pre_balance UInt64,
+    user_coin_pre_balance UInt64,
+    coin_symbol String DEFAULT '',
+    pc_symbol String DEFAULT '',
+    pool_age_slots Nullable(UInt64),
+    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_pc_mint (SELECT * ORDER BY pc_mint, slot, transaction_index, instruction_index),
+    PROJECTION projection_coin_mint (SELECT * ORDER BY coin_mint, slot, transaction_index, instruction_index),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_amm_withdraw_pnl_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    amm LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    pc_amount UInt64,
+    coin_amount UInt64,
+    pc_mint LowCardinality(String) CODEC(LZ4),
+    coin_mint LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_pc_mint (SELECT * ORDER BY pc_mint, slot, transaction_index, instruction_index),
+    PROJECTION projection_coin_mint (SELECT * ORDER BY coin_mint, slot, transaction_index, instruction_index),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_amm_other_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    "type" String,
+    amm LowCardinality(String) CODEC(LZ4),
+    param LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    value Nullable(UInt64),
+    new_amm_owner LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    min_separate_numerator Nullable(UInt64),
+    min_separate_denominator Nullable(UInt64),
+    trade_fee_numerator Nullable(UInt64),
+    trade_fee_denominator Nullable(UInt64),
+    pnl_numerator Nullable(UInt64),
+    pnl_denominator Nullable(UInt64),
+    swap_fee_numerator Nullable(UInt64),
+    swap_fee_denominator Nullable(UInt64),
+    last_order_numerator Nullable(UInt64),
+    last_order_denominator Nullable(UInt64),
+    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
+    -- PROJECTION projection_type (SELECT * ORDER BY "type"),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+-- RAYDIUM CLMM EVENTS
+
+CREATE TABLE raydium_clmm_swap_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    amount_in UInt64,
+    amount_out UInt64,
+    ui_amount_in Nullable(Decimal(38, 18)),
+    ui_amount_out Nullable(Decimal(38, 18)),
+    usd_value Nullable(Float64),
+    mint_in LowCardinality(String) CODEC(LZ4),
+    mint_out LowCardinality(String) CODEC(LZ4),
+    is_base_input Boolean,
+    other_amount_threshold UInt64,
+    sqrt_price_limit_x64 UInt128,
+    sqrt_price_x64 Nullable(UInt128),
+    liquidity Nullable(UInt128),
+    tick Nullable(Int32),
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_in (SELECT * ORDER BY mint_in, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_out (SELECT * ORDER BY mint_out, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_clmm_create_pool_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    creator LowCardinality(String) CODEC(LZ4),
+    amm_config LowCardinality(String) CODEC(LZ4),
+    token_mint_0 LowCardinality(String) CODEC(LZ4),
+    token_mint_1 LowCardinality(String) CODEC(LZ4),
+    token_vault_0 String,
+    token_vault_1 String,
+    sqrt_price_x64 UInt128,
+    open_time UInt64,
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_token_mint_0 (SELECT * ORDER BY token_mint_0, slot, transaction_index, instruction_index),
+    PROJECTION projection_token_mint_1 (SELECT * ORDER BY token_mint_1, slot, transaction_index, instruction_index),
+    PROJECTION projection_creator (SELECT * ORDER BY creator, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_clmm_open_position_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    owner LowCardinality(String) CODEC(LZ4),
+    position_nft_mint String,
+    personal_position String,
+    tick_lower_index Int32,
+    tick_upper_index Int32,
+    liquidity UInt128,
+    amount_0 UInt64,
+    amount_1 UInt64,
+    amount_0_max UInt64,
+    amount_1_max UInt64,
+    token_mint_0 LowCardinality(String) CODEC(LZ4),
+    token_mint_1 LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_clmm_close_position_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    owner LowCardinality(String) CODEC(LZ4),
+    position_nft_mint String,
+    personal_position String,
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_clmm_increase_liquidity_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    owner LowCardinality(String) CODEC(LZ4),
+    personal_position String,
+    liquidity UInt128,
+    amount_0 UInt64,
+    amount_1 UInt64,
+    amount_0_max UInt64,
+    amount_1_max UInt64,
+    token_mint_0 LowCardinality(String) CODEC(LZ4),
+    token_mint_1 LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_clmm_decrease_liquidity_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    owner LowCardinality(String) CODEC(LZ4),
+    personal_position String,
+    liquidity UInt128,
+    amount_0 UInt64,
+    amount_1 UInt64,
+    amount_0_min UInt64,
+    amount_1_min UInt64,
+    token_mint_0 LowCardinality(String) CODEC(LZ4),
+    token_mint_1 LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_clmm_collect_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    "type" String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    amount_0 UInt64,
+    amount_1 UInt64,
+    token_mint_0 LowCardinality(String) CODEC(LZ4),
+    token_mint_1 LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+-- RAYDIUM CPMM EVENTS
+
+CREATE TABLE raydium_cpmm_swap_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    amount_in UInt64,
+    amount_out UInt64,
+    ui_amount_in Nullable(Decimal(38, 18)),
+    ui_amount_out Nullable(Decimal(38, 18)),
+    usd_value Nullable(Float64),
+    mint_in LowCardinality(String) CODEC(LZ4),
+    mint_out LowCardinality(String) CODEC(LZ4),
+    swap_type LowCardinality(String) CODEC(LZ4),
+    minimum_amount_out Nullable(UInt64),
+    max_amount_in Nullable(UInt64),
+    input_vault_balance Nullable(UInt64),
+    output_vault_balance Nullable(UInt64),
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_in (SELECT * ORDER BY mint_in, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_out (SELECT * ORDER BY mint_out, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_cpmm_initialize_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    creator LowCardinality(String) CODEC(LZ4),
+    amm_config LowCardinality(String) CODEC(LZ4),
+    token_0_mint LowCardinality(String) CODEC(LZ4),
+    token_1_mint LowCardinality(String) CODEC(LZ4),
+    lp_mint LowCardinality(String) CODEC(LZ4),
+    init_amount_0 UInt64,
+    init_amount_1 UInt64,
+    open_time UInt64,
+    token_0_vault_balance Nullable(UInt64),
+    token_1_vault_balance Nullable(UInt64),
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_token_0_mint (SELECT * ORDER BY token_0_mint, slot, transaction_index, instruction_index),
+    PROJECTION projection_token_1_mint (SELECT * ORDER BY token_1_mint, slot, transaction_index, instruction_index),
+    PROJECTION projection_creator (SELECT * ORDER BY creator, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_cpmm_deposit_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    owner LowCardinality(String) CODEC(LZ4),
+    token_0_mint LowCardinality(String) CODEC(LZ4),
+    token_1_mint LowCardinality(String) CODEC(LZ4),
+    lp_mint LowCardinality(String) CODEC(LZ4),
+    lp_token_amount UInt64,
+    amount_0 UInt64,
+    amount_1 UInt64,
+    maximum_amount_0 UInt64,
+    maximum_amount_1 UInt64,
+    token_0_vault_balance Nullable(UInt64),
+    token_1_vault_balance Nullable(UInt64),
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE raydium_cpmm_withdraw_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool_state LowCardinality(String) CODEC(LZ4),
+    owner LowCardinality(String) CODEC(LZ4),
+    token_0_mint LowCardinality(String) CODEC(LZ4),
+    token_1_mint LowCardinality(String) CODEC(LZ4),
+    lp_mint LowCardinality(String) CODEC(LZ4),
+    lp_token_amount UInt64,
+    amount_0 UInt64,
+    amount_1 UInt64,
+    minimum_amount_0 UInt64,
+    minimum_amount_1 UInt64,
+    token_0_vault_balance Nullable(UInt64),
+    token_1_vault_balance Nullable(UInt64),
+    PROJECTION projection_pool_state (SELECT * ORDER BY pool_state, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+-- WHIRLPOOL EVENTS
+
+CREATE TABLE whirlpool_swap_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    whirlpool LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    amount_in UInt64,
+    amount_out UInt64,
+    ui_amount_in Nullable(Decimal(38, 18)),
+    ui_amount_out Nullable(Decimal(38, 18)),
+    usd_value Nullable(Float64),
+    mint_in LowCardinality(String) CODEC(LZ4),
+    mint_out LowCardinality(String) CODEC(LZ4),
+    a_to_b Boolean,
+    amount_specified_is_input Boolean,
+    other_amount_threshold UInt64,
+    sqrt_price_limit UInt128,
+    pre_sqrt_price Nullable(UInt128),
+    post_sqrt_price Nullable(UInt128),
+    PROJECTION projection_whirlpool (SELECT * ORDER BY whirlpool, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_in (SELECT * ORDER BY mint_in, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_out (SELECT * ORDER BY mint_out, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE whirlpool_two_hop_swap_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    whirlpool_one LowCardinality(String) CODEC(LZ4),
+    whirlpool_two LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    amount_in UInt64,
+    amount_intermediate UInt64,
+    amount_out UInt64,
+    ui_amount_in Nullable(Decimal(38, 18)),
+    ui_amount_out Nullable(Decimal(38, 18)),
+    usd_value Nullable(Float64),
+    mint_in LowCardinality(String) CODEC(LZ4),
+    mint_intermediate LowCardinality(String) CODEC(LZ4),
+    mint_out LowCardinality(String) CODEC(LZ4),
+    a_to_b_one Boolean,
+    a_to_b_two Boolean,
+    amount_specified_is_input Boolean,
+    other_amount_threshold UInt64,
+    sqrt_price_limit_one UInt128,
+    sqrt_price_limit_two UInt128,
+    post_sqrt_price_one Nullable(UInt128),
+    post_sqrt_price_two Nullable(UInt128),
+    PROJECTION projection_whirlpool_one (SELECT * ORDER BY whirlpool_one, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_whirlpool_two (SELECT * ORDER BY whirlpool_two, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE whirlpool_initialize_pool_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    whirlpool LowCardinality(String) CODEC(LZ4),
+    whirlpools_config LowCardinality(String) CODEC(LZ4),
+    funder LowCardinality(String) CODEC(LZ4),
+    token_mint_a LowCardinality(String) CODEC(LZ4),
+    token_mint_b LowCardinality(String) CODEC(LZ4),
+    token_vault_a String,
+    token_vault_b String,
+    tick_spacing UInt16,
+    initial_sqrt_price UInt128,
+    PROJECTION projection_whirlpool (SELECT * ORDER BY whirlpool, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_token_mint_a (SELECT * ORDER BY token_mint_a, slot, transaction_index, instruction_index),
+    PROJECTION projection_token_mint_b (SELECT * ORDER BY token_mint_b, slot, transaction_index, instruction_index),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE whirlpool_open_position_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    whirlpool LowCardinality(String) CODEC(LZ4),
+    owner LowCardinality(String) CODEC(LZ4),
+    position String,
+    position_mint String,
+    tick_lower_index Int32,
+    tick_upper_index Int32,
+    PROJECTION projection_whirlpool (SELECT * ORDER BY whirlpool, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE whirlpool_close_position_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    position_authority LowCardinality(String) CODEC(LZ4),
+    receiver LowCardinality(String) CODEC(LZ4),
+    position String,
+    position_mint String,
+    PROJECTION projection_position_authority (SELECT * ORDER BY position_authority, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE whirlpool_increase_liquidity_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    whirlpool LowCardinality(String) CODEC(LZ4),
+    position_authority LowCardinality(String) CODEC(LZ4),
+    position String,
+    liquidity UInt128,
+    token_a_amount UInt64,
+    token_b_amount UInt64,
+    token_max_a UInt64,
+    token_max_b UInt64,
+    token_mint_a LowCardinality(String) CODEC(LZ4),
+    token_mint_b LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_whirlpool (SELECT * ORDER BY whirlpool, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_position_authority (SELECT * ORDER BY position_authority, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE whirlpool_decrease_liquidity_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    whirlpool LowCardinality(String) CODEC(LZ4),
+    position_authority LowCardinality(String) CODEC(LZ4),
+    position String,
+    liquidity UInt128,
+    token_a_amount UInt64,
+    token_b_amount UInt64,
+    token_min_a UInt64,
+    token_min_b UInt64,
+    token_mint_a LowCardinality(String) CODEC(LZ4),
+    token_mint_b LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_whirlpool (SELECT * ORDER BY whirlpool, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_position_authority (SELECT * ORDER BY position_authority, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE whirlpool_collect_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    "type" String,
+    whirlpool LowCardinality(String) CODEC(LZ4),
+    position_authority LowCardinality(String) CODEC(LZ4),
+    position String,
+    reward_index Nullable(UInt8),
+    token_a_amount UInt64,
+    token_b_amount UInt64,
+    token_mint_a LowCardinality(String) CODEC(LZ4),
+    token_mint_b LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_whirlpool (SELECT * ORDER BY whirlpool, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_position_authority (SELECT * ORDER BY position_authority, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+-- METEORA DLMM EVENTS
+
+CREATE TABLE meteora_dlmm_swap_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    lb_pair LowCardinality(String) CODEC(LZ4),
+    user LowCardinality(String) CODEC(LZ4),
+    amount_in UInt64,
+    amount_out UInt64,
+    ui_amount_in Nullable(Decimal(38, 18)),
+    ui_amount_out Nullable(Decimal(38, 18)),
+    usd_value Nullable(Float64),
+    mint_in LowCardinality(String) CODEC(LZ4),
+    mint_out LowCardinality(String) CODEC(LZ4),
+    swap_for_y Boolean,
+    start_bin_id Int32,
+    end_bin_id Int32,
+    fee UInt64,
+    protocol_fee UInt64,
+    host_fee UInt64,
+    fee_bps UInt128,
+    PROJECTION projection_lb_pair (SELECT * ORDER BY lb_pair, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_in (SELECT * ORDER BY mint_in, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_out (SELECT * ORDER BY mint_out, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE meteora_dlmm_add_liquidity_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    lb_pair LowCardinality(String) CODEC(LZ4),
+    sender LowCardinality(String) CODEC(LZ4),
+    position String,
+    amount_x UInt64,
+    amount_y UInt64,
+    active_bin_id Int32,
+    min_bin_id Nullable(Int32),
+    max_bin_id Nullable(Int32),
+    PROJECTION projection_lb_pair (SELECT * ORDER BY lb_pair, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_sender (SELECT * ORDER BY sender, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE meteora_dlmm_remove_liquidity_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    lb_pair LowCardinality(String) CODEC(LZ4),
+    sender LowCardinality(String) CODEC(LZ4),
+    position String,
+    amount_x UInt64,
+    amount_y UInt64,
+    active_bin_id Int32,
+    min_bin_id Nullable(Int32),
+    max_bin_id Nullable(Int32),
+    PROJECTION projection_lb_pair (SELECT * ORDER BY lb_pair, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_sender (SELECT * ORDER BY sender, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE meteora_dlmm_lb_pair_create_events
 (
     slot UInt64,
     transaction_index UInt64,
     instruction_index UInt64,
     partial_signature String,
     partial_blockhash String,
-    amm LowCardinality(String) CODEC(LZ4),
-    user LowCardinality(String) CODEC(LZ4),
-    amount_in UInt64,
-    amount_out UInt64,
-    mint_in LowCardinality(String) CODEC(LZ4),
-    mint_out LowCardinality(String) CODEC(LZ4),
-    direction LowCardinality(String) CODEC(LZ4),
-    pool_pc_amount UInt64,
-    pool_coin_amount UInt64,
-    user_pre_balance_in UInt64,
-    user_pre_balance_out UInt64,
-    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_mint_in (SELECT * ORDER BY mint_in, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_mint_out (SELECT * ORDER BY mint_out, slot, transaction_index, instruction_index), -- RECOMMENDED
+    lb_pair String,
+    token_x LowCardinality(String) CODEC(LZ4),
+    token_y LowCardinality(String) CODEC(LZ4),
+    bin_step UInt16,
+    active_id Nullable(Int32),
+    PROJECTION projection_token_x (SELECT * ORDER BY token_x, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_token_y (SELECT * ORDER BY token_y, slot, transaction_index, instruction_index), -- RECOMMENDED
     parent_instruction_index Int64 DEFAULT -1,
     top_instruction_index Int64 DEFAULT -1,
     parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
     top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
 )
 ENGINE = MergeTree
-PARTITION BY toInt64(slot / 1e6)
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
-CREATE TABLE raydium_amm_initialize_events
+CREATE TABLE meteora_dlmm_position_create_events
 (
     slot UInt64,
     transaction_index UInt64,
     instruction_index UInt64,
     partial_signature String,
     partial_blockhash String,
-    amm LowCardinality(String) CODEC(LZ4),
-    user LowCardinality(String) CODEC(LZ4),
-    pc_init_amount UInt64,
-    coin_init_amount UInt64,
-    lp_init_amount UInt64,
-    pc_mint LowCardinality(String) CODEC(LZ4),
-    coin_mint LowCardinality(String) CODEC(LZ4),
-    lp_mint LowCardinality(String) CODEC(LZ4),
-    user_pc_pre_balance UInt64,
-    user_coin_pre_balance UInt64,
-    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_pc_mint (SELECT * ORDER BY pc_mint, slot, transaction_index, instruction_index),
-    PROJECTION projection_coin_mint (SELECT * ORDER BY coin_mint, slot, transaction_index, instruction_index),
-    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    lb_pair LowCardinality(String) CODEC(LZ4),
+    position String,
+    owner LowCardinality(String) CODEC(LZ4),
+    lower_bin_id Nullable(Int32),
+    upper_bin_id Nullable(Int32),
+    PROJECTION projection_lb_pair (SELECT * ORDER BY lb_pair, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
     parent_instruction_index Int64 DEFAULT -1,
     top_instruction_index Int64 DEFAULT -1,
     parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
@@ -104,29 +1166,16 @@ ENGINE = MergeTree
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
-CREATE TABLE raydium_amm_deposit_events
+CREATE TABLE meteora_dlmm_position_close_events
 (
     slot UInt64,
     transaction_index UInt64,
     instruction_index UInt64,
     partial_signature String,
     partial_blockhash String,
-    amm LowCardinality(String) CODEC(LZ4),
-    user LowCardinality(String) CODEC(LZ4),
-    pc_amount UInt64,
-    coin_amount UInt64,
-    pool_pc_amount UInt64,
-    pool_coin_amount UInt64,
-    lp_amount UInt64,
-    pc_mint LowCardinality(String) CODEC(LZ4),
-    coin_mint LowCardinality(String) CODEC(LZ4),
-    lp_mint LowCardinality(String) CODEC(LZ4),
-    user_pc_pre_balance UInt64,
-    user_coin_pre_balance UInt64,
-    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_pc_mint (SELECT * ORDER BY pc_mint, slot, transaction_index, instruction_index),
-    PROJECTION projection_coin_mint (SELECT * ORDER BY coin_mint, slot, transaction_index, instruction_index),
+    position String,
+    owner LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
     parent_instruction_index Int64 DEFAULT -1,
     top_instruction_index Int64 DEFAULT -1,
     parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
@@ -136,29 +1185,22 @@ ENGINE = MergeTree
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
-CREATE TABLE raydium_amm_withdraw_events
+CREATE TABLE meteora_dlmm_claim_events
 (
     slot UInt64,
     transaction_index UInt64,
     instruction_index UInt64,
     partial_signature String,
     partial_blockhash String,
-    amm LowCardinality(String) CODEC(LZ4),
-    user LowCardinality(String) CODEC(LZ4),
-    pc_amount UInt64,
-    coin_amount UInt64,
-    lp_amount UInt64,
-    pool_pc_amount UInt64,
-    pool_coin_amount UInt64,
-    pc_mint LowCardinality(String) CODEC(LZ4),
-    coin_mint LowCardinality(String) CODEC(LZ4),
-    lp_mint LowCardinality(String) CODEC(LZ4),
-    user_pc_pre_balance UInt64,
-    user_coin_pre_balance UInt64,
-    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_pc_mint (SELECT * ORDER BY pc_mint, slot, transaction_index, instruction_index),
-    PROJECTION projection_coin_mint (SELECT * ORDER BY coin_mint, slot, transaction_index, instruction_index),
+    "type" String,
+    lb_pair LowCardinality(String) CODEC(LZ4),
+    position String,
+    owner LowCardinality(String) CODEC(LZ4),
+    reward_index Nullable(UInt64),
+    amount_x UInt64,
+    amount_y UInt64,
+    PROJECTION projection_lb_pair (SELECT * ORDER BY lb_pair, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
     parent_instruction_index Int64 DEFAULT -1,
     top_instruction_index Int64 DEFAULT -1,
     parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
@@ -168,23 +1210,143 @@ ENGINE = MergeTree
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
-CREATE TABLE raydium_amm_withdraw_pnl_events
+-- AGGREGATOR TRADES
+
+CREATE TABLE aggregator_trades
 (
     slot UInt64,
     transaction_index UInt64,
     instruction_index UInt64,
     partial_signature String,
     partial_blockhash String,
-    amm LowCardinality(String) CODEC(LZ4),
-    user LowCardinality(String) CODEC(LZ4),
-    pc_amount UInt64,
-    coin_amount UInt64,
-    pc_mint LowCardinality(String) CODEC(LZ4),
-    coin_mint LowCardinality(String) CODEC(LZ4),
-    PROJECTION projection_amm (SELECT * ORDER BY amm, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
-    PROJECTION projection_pc_mint (SELECT * ORDER BY pc_mint, slot, transaction_index, instruction_index),
-    PROJECTION projection_coin_mint (SELECT * ORDER BY coin_mint, slot, transaction_index, instruction_index),
+    aggregator LowCardinality(String) CODEC(LZ4),
+    "type" String,
+    trader LowCardinality(String) CODEC(LZ4),
+    amount_in UInt64,
+    amount_out UInt64,
+    ui_amount_in Nullable(Decimal(38, 18)),
+    ui_amount_out Nullable(Decimal(38, 18)),
+    usd_value Nullable(Float64),
+    mint_in LowCardinality(String) CODEC(LZ4),
+    mint_out LowCardinality(String) CODEC(LZ4),
+    quoted_amount UInt64,
+    slippage_bps UInt16,
+    platform_fee_bps UInt8,
+    platform_fee_amount Nullable(UInt64),
+    platform_fee_mint LowCardinality(String) CODEC(LZ4),
+    hops UInt32,
+    PROJECTION projection_trader (SELECT * ORDER BY trader, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_in (SELECT * ORDER BY mint_in, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint_out (SELECT * ORDER BY mint_out, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+-- ORDER BOOK EVENTS
+
+CREATE TABLE order_book_orders
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    program LowCardinality(String) CODEC(LZ4),
+    "type" String,
+    market LowCardinality(String) CODEC(LZ4),
+    trader LowCardinality(String) CODEC(LZ4),
+    side LowCardinality(String),
+    order_type LowCardinality(String),
+    price_in_ticks Nullable(Int64),
+    price Nullable(Float64),
+    size_in_lots UInt64,
+    size Nullable(Float64),
+    filled_size_in_lots UInt64,
+    number_of_fills UInt32,
+    client_order_id Nullable(UInt128),
+    PROJECTION projection_market (SELECT * ORDER BY market, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_trader (SELECT * ORDER BY trader, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE order_book_fills
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    fill_index UInt64,
+    program LowCardinality(String) CODEC(LZ4),
+    market LowCardinality(String) CODEC(LZ4),
+    maker LowCardinality(String) CODEC(LZ4),
+    taker LowCardinality(String) CODEC(LZ4),
+    taker_side LowCardinality(String),
+    price_in_ticks Int64,
+    price Nullable(Float64),
+    size_in_lots UInt64,
+    size Nullable(Float64),
+    PROJECTION projection_market (SELECT * ORDER BY market, slot, transaction_index, instruction_index, fill_index), -- RECOMMENDED
+    PROJECTION projection_maker (SELECT * ORDER BY maker, slot, transaction_index, instruction_index, fill_index),
+    PROJECTION projection_taker (SELECT * ORDER BY taker, slot, transaction_index, instruction_index, fill_index),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index, fill_index)
+ORDER BY (slot, transaction_index, instruction_index, fill_index);
+
+CREATE TABLE order_book_cancels
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    program LowCardinality(String) CODEC(LZ4),
+    "type" String,
+    market LowCardinality(String) CODEC(LZ4),
+    trader LowCardinality(String) CODEC(LZ4),
+    side LowCardinality(String) DEFAULT '',
+    order_id Nullable(UInt128),
+    client_order_id Nullable(UInt64),
+    orders_cancelled Nullable(UInt32),
+    lots_removed Nullable(UInt64),
+    PROJECTION projection_market (SELECT * ORDER BY market, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_trader (SELECT * ORDER BY trader, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE order_book_settle_funds
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    program LowCardinality(String) CODEC(LZ4),
+    market LowCardinality(String) CODEC(LZ4),
+    owner LowCardinality(String) CODEC(LZ4),
+    base_amount UInt64,
+    quote_amount UInt64,
+    PROJECTION projection_market (SELECT * ORDER BY market, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_owner (SELECT * ORDER BY owner, slot, transaction_index, instruction_index), -- RECOMMENDED
     parent_instruction_index Int64 DEFAULT -1,
     top_instruction_index Int64 DEFAULT -1,
     parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
@@ -274,6 +1436,8 @@ CREATE TABLE spl_token_transfer_events
     destination_pre_balance UInt64,
     mint LowCardinality(String) CODEC(LZ4),
     amount UInt64,
+    ui_amount Nullable(Decimal(38, 18)),
+    usd_value Nullable(Float64),
     authority LowCardinality(String) CODEC(LZ4),
     transfer_type LowCardinality(String) DEFAULT 'unknown' CODEC(LZ4),
     PROJECTION projection_mint (SELECT * ORDER BY mint, slot, transaction_index, instruction_index), -- RECOMMENDED
@@ -365,6 +1529,7 @@ CREATE TABLE spl_token_mint_to_events
     mint LowCardinality(String) CODEC(LZ4),
     mint_authority LowCardinality(String) CODEC(LZ4),
     amount UInt64,
+    ui_amount Nullable(Decimal(38, 18)),
     PROJECTION projection_mint (SELECT * ORDER BY mint, slot, transaction_index, instruction_index), -- RECOMMENDED
     PROJECTION projection_destination (SELECT * ORDER BY destination_owner, slot, transaction_index, instruction_index), -- RECOMMENDED
     parent_instruction_index Int64 DEFAULT -1,
@@ -390,6 +1555,7 @@ CREATE TABLE spl_token_burn_events
     mint LowCardinality(String) CODEC(LZ4),
     authority LowCardinality(String) CODEC(LZ4),
     amount UInt64,
+    ui_amount Nullable(Decimal(38, 18)),
     PROJECTION projection_mint (SELECT * ORDER BY mint, slot, transaction_index, instruction_index), -- RECOMMENDED
     PROJECTION projection_source (SELECT * ORDER BY source_owner, slot, transaction_index, instruction_index), -- RECOMMENDED
     parent_instruction_index Int64 DEFAULT -1,
@@ -422,23 +1588,137 @@ CREATE TABLE spl_token_close_account_events
     top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
 )
 ENGINE = MergeTree
-PARTITION BY toInt64(slot / 4e6)
+PARTITION BY toInt64(slot / 4e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE spl_token_freeze_account_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    source_address LowCardinality(String) CODEC(LZ4),
+    source_owner LowCardinality(String) CODEC(LZ4),
+    mint LowCardinality(String) CODEC(LZ4),
+    freeze_authority LowCardinality(String) CODEC(LZ4),
+    -- PROJECTION projection_mint (SELECT * ORDER BY mint),
+    -- PROJECTION projection_source (SELECT * ORDER BY source_owner),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE spl_token_thaw_account_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    source_address LowCardinality(String) CODEC(LZ4),
+    source_owner LowCardinality(String) CODEC(LZ4),
+    mint LowCardinality(String) CODEC(LZ4),
+    freeze_authority LowCardinality(String) CODEC(LZ4),
+    -- PROJECTION projection_mint (SELECT * ORDER BY mint),
+    -- PROJECTION projection_source (SELECT * ORDER BY source_owner),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE spl_token_initialize_immutable_owner_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    account_address LowCardinality(String) CODEC(LZ4),
+    account_owner LowCardinality(String) CODEC(LZ4),
+    mint LowCardinality(String) CODEC(LZ4),
+    -- PROJECTION projection_mint (SELECT * ORDER BY mint),
+    -- PROJECTION projection_owner (SELECT * ORDER BY account_owner),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 8e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE spl_token_sync_native_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    account_address LowCardinality(String) CODEC(LZ4),
+    account_owner LowCardinality(String) CODEC(LZ4),
+    -- PROJECTION projection_owner (SELECT * ORDER BY account_owner),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+-- COMPUTE BUDGET EVENTS
+
+CREATE TABLE compute_budget_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    "type" String,
+    compute_unit_limit Nullable(UInt32),
+    compute_unit_price Nullable(UInt64),
+    heap_frame_bytes Nullable(UInt32),
+    loaded_accounts_data_size_limit Nullable(UInt32),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
-CREATE TABLE spl_token_freeze_account_events
+-- ADDRESS LOOKUP TABLE EVENTS
+
+CREATE TABLE address_lookup_table_events
 (
     slot UInt64,
     transaction_index UInt64,
     instruction_index UInt64,
     partial_signature String,
     partial_blockhash String,
-    source_address LowCardinality(String) CODEC(LZ4),
-    source_owner LowCardinality(String) CODEC(LZ4),
-    mint LowCardinality(String) CODEC(LZ4),
-    freeze_authority LowCardinality(String) CODEC(LZ4),
-    -- PROJECTION projection_mint (SELECT * ORDER BY mint),
-    -- PROJECTION projection_source (SELECT * ORDER BY source_owner),
+    "type" String,
+    lookup_table LowCardinality(String) CODEC(LZ4),
+    authority LowCardinality(String) CODEC(LZ4),
+    payer LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    recipient LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    recent_slot Nullable(UInt64),
+    new_addresses Array(String),
+    PROJECTION projection_lookup_table (SELECT * ORDER BY lookup_table, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_authority (SELECT * ORDER BY authority, slot, transaction_index, instruction_index),
     parent_instruction_index Int64 DEFAULT -1,
     top_instruction_index Int64 DEFAULT -1,
     parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
@@ -448,66 +1728,91 @@ ENGINE = MergeTree
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
-CREATE TABLE spl_token_thaw_account_events
+-- ASSOCIATED TOKEN ACCOUNT EVENTS
+
+CREATE TABLE associated_token_account_events
 (
     slot UInt64,
     transaction_index UInt64,
     instruction_index UInt64,
     partial_signature String,
     partial_blockhash String,
-    source_address LowCardinality(String) CODEC(LZ4),
-    source_owner LowCardinality(String) CODEC(LZ4),
+    "type" String,
+    wallet LowCardinality(String) CODEC(LZ4),
     mint LowCardinality(String) CODEC(LZ4),
-    freeze_authority LowCardinality(String) CODEC(LZ4),
-    -- PROJECTION projection_mint (SELECT * ORDER BY mint),
-    -- PROJECTION projection_source (SELECT * ORDER BY source_owner),
+    associated_token_account LowCardinality(String) CODEC(LZ4),
+    payer LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    token_program LowCardinality(String) CODEC(LZ4),
+    already_existed Boolean,
+    nested_token_account LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    owner_mint LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    PROJECTION projection_wallet (SELECT * ORDER BY wallet, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_mint (SELECT * ORDER BY mint, slot, transaction_index, instruction_index),
+    PROJECTION projection_associated_token_account (SELECT * ORDER BY associated_token_account, slot, transaction_index, instruction_index), -- RECOMMENDED
     parent_instruction_index Int64 DEFAULT -1,
     top_instruction_index Int64 DEFAULT -1,
     parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
     top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
 )
 ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
-CREATE TABLE spl_token_initialize_immutable_owner_events
+-- PROGRAM DEPLOY EVENTS
+
+CREATE TABLE program_deploy_events
 (
     slot UInt64,
     transaction_index UInt64,
     instruction_index UInt64,
     partial_signature String,
     partial_blockhash String,
-    account_address LowCardinality(String) CODEC(LZ4),
-    account_owner LowCardinality(String) CODEC(LZ4),
-    mint LowCardinality(String) CODEC(LZ4),
-    -- PROJECTION projection_mint (SELECT * ORDER BY mint),
-    -- PROJECTION projection_owner (SELECT * ORDER BY account_owner),
+    "type" String,
+    program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    programdata LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    buffer LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    account LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    authority LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    new_authority LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    payer LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    recipient LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    data_length Nullable(UInt64),
+    write_offset Nullable(UInt32),
+    write_count Nullable(UInt32),
+    PROJECTION projection_program_id (SELECT * ORDER BY program_id, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_buffer (SELECT * ORDER BY buffer, slot, transaction_index, instruction_index),
+    PROJECTION projection_authority (SELECT * ORDER BY authority, slot, transaction_index, instruction_index),
     parent_instruction_index Int64 DEFAULT -1,
     top_instruction_index Int64 DEFAULT -1,
     parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
     top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
 )
 ENGINE = MergeTree
-PARTITION BY toInt64(slot / 8e6)
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
-CREATE TABLE spl_token_sync_native_events
+-- MEMO EVENTS
+
+CREATE TABLE memo_events
 (
     slot UInt64,
     transaction_index UInt64,
     instruction_index UInt64,
     partial_signature String,
     partial_blockhash String,
-    account_address LowCardinality(String) CODEC(LZ4),
-    account_owner LowCardinality(String) CODEC(LZ4),
-    -- PROJECTION projection_owner (SELECT * ORDER BY account_owner),
+    version UInt8,
+    memo String,
+    is_lossy Boolean,
+    number_of_signers UInt8,
+    signers Array(String),
     parent_instruction_index Int64 DEFAULT -1,
     top_instruction_index Int64 DEFAULT -1,
     parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
     top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
 )
 ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
@@ -569,6 +1874,7 @@ CREATE TABLE system_program_transfer_events
     recipient_account_pre_balance UInt64,
     recipient_account_post_balance UInt64,
     lamports UInt64,
+    usd_value Nullable(Float64),
     transfer_type LowCardinality(String) DEFAULT 'unknown' CODEC(LZ4),
     PROJECTION projection_funding_account (SELECT * ORDER BY funding_account, slot, transaction_index, instruction_index), -- RECOMMENDED
     PROJECTION projection_recipient_account (SELECT * ORDER BY recipient_account, slot, transaction_index, instruction_index), -- RECOMMENDED
@@ -755,6 +2061,7 @@ CREATE TABLE system_program_transfer_with_seed_events
     recipient_account_pre_balance UInt64,
     recipient_account_post_balance UInt64,
     lamports UInt64,
+    usd_value Nullable(Float64),
     from_seed String CODEC(LZ4),
     from_owner LowCardinality(String) CODEC(LZ4),
     transfer_type LowCardinality(String) DEFAULT 'unknown' CODEC(LZ4),
@@ -786,8 +2093,224 @@ ENGINE = MergeTree
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
+-- STAKE PROGRAM EVENTS
+
+CREATE TABLE stake_program_initialize_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    "type" String,
+    stake_account LowCardinality(String) CODEC(LZ4),
+    staker LowCardinality(String) CODEC(LZ4),
+    withdrawer LowCardinality(String) CODEC(LZ4),
+    lockup_unix_timestamp Int64,
+    lockup_epoch UInt64,
+    lockup_custodian LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_staker (SELECT * ORDER BY staker, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_withdrawer (SELECT * ORDER BY withdrawer, slot, transaction_index, instruction_index),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE stake_program_authorize_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    "type" String,
+    stake_account LowCardinality(String) CODEC(LZ4),
+    authority LowCardinality(String) CODEC(LZ4),
+    new_authority LowCardinality(String) CODEC(LZ4),
+    authority_type LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_stake_account (SELECT * ORDER BY stake_account, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_new_authority (SELECT * ORDER BY new_authority, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE stake_program_delegate_stake_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    stake_account LowCardinality(String) CODEC(LZ4),
+    vote_account LowCardinality(String) CODEC(LZ4),
+    stake_authority LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_vote_account (SELECT * ORDER BY vote_account, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_stake_account (SELECT * ORDER BY stake_account, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_stake_authority (SELECT * ORDER BY stake_authority, slot, transaction_index, instruction_index),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 4e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE stake_program_split_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    stake_account LowCardinality(String) CODEC(LZ4),
+    split_stake_account LowCardinality(String) CODEC(LZ4),
+    stake_authority LowCardinality(String) CODEC(LZ4),
+    lamports UInt64,
+    usd_value Nullable(Float64),
+    PROJECTION projection_stake_account (SELECT * ORDER BY stake_account, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE stake_program_withdraw_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    stake_account LowCardinality(String) CODEC(LZ4),
+    recipient_account LowCardinality(String) CODEC(LZ4),
+    withdraw_authority LowCardinality(String) CODEC(LZ4),
+    lamports UInt64,
+    usd_value Nullable(Float64),
+    PROJECTION projection_stake_account (SELECT * ORDER BY stake_account, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_recipient_account (SELECT * ORDER BY recipient_account, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE stake_program_deactivate_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    stake_account LowCardinality(String) CODEC(LZ4),
+    stake_authority LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_stake_account (SELECT * ORDER BY stake_account, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE stake_program_set_lockup_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    "type" String,
+    stake_account LowCardinality(String) CODEC(LZ4),
+    authority LowCardinality(String) CODEC(LZ4),
+    unix_timestamp Nullable(Int64),
+    epoch Nullable(UInt64),
+    custodian LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    PROJECTION projection_stake_account (SELECT * ORDER BY stake_account, slot, transaction_index, instruction_index),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE stake_program_merge_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    destination_stake_account LowCardinality(String) CODEC(LZ4),
+    source_stake_account LowCardinality(String) CODEC(LZ4),
+    stake_authority LowCardinality(String) CODEC(LZ4),
+    PROJECTION projection_destination_stake_account (SELECT * ORDER BY destination_stake_account, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_source_stake_account (SELECT * ORDER BY source_stake_account, slot, transaction_index, instruction_index),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
 -- PUMPFUN EVENTS
 
+CREATE TABLE pumpfun_tokens
+(
+    mint String,
+    creator LowCardinality(String) CODEC(LZ4),
+    bonding_curve String,
+    creation_slot Nullable(UInt64),
+    virtual_sol_reserves UInt64,
+    virtual_token_reserves UInt64,
+    real_sol_reserves UInt64,
+    real_token_reserves UInt64,
+    bonding_progress Nullable(Float64),
+    market_cap_sol Nullable(Float64),
+    complete_slot Nullable(UInt64),
+    raydium_amm String DEFAULT '',
+    pumpswap_pool String DEFAULT '',
+    slot UInt64,
+    PROJECTION projection_creator (SELECT * ORDER BY creator, mint), -- RECOMMENDED
+)
+ENGINE = ReplacingMergeTree(slot)
+PRIMARY KEY mint
+ORDER BY mint;
+
+CREATE TABLE pumpfun_daily_revenue
+(
+    mint String,
+    timestamp DateTime,
+    fee_lamports UInt64,
+    sol_volume UInt64,
+    swap_count UInt64,
+    slot UInt64,
+)
+ENGINE = ReplacingMergeTree(slot)
+PRIMARY KEY (timestamp, mint)
+ORDER BY (timestamp, mint);
+
 CREATE TABLE pumpfun_create_events
 (
     slot UInt64,
@@ -869,6 +2392,10 @@ CREATE TABLE pumpfun_swap_events
     token_amount UInt64,
     direction String CODEC(LZ4),
     sol_amount UInt64,
+    ui_token_amount Nullable(Decimal(38, 18)),
+    ui_sol_amount Decimal(38, 18),
+    usd_value Nullable(Float64),
+    fee_lamports UInt64,
     virtual_sol_reserves UInt64,
     virtual_token_reserves UInt64,
     real_sol_reserves UInt64,
@@ -903,6 +2430,127 @@ ENGINE = MergeTree
 PRIMARY KEY (slot, transaction_index, instruction_index)
 ORDER BY (slot, transaction_index, instruction_index);
 
+-- PUMPSWAP EVENTS
+
+CREATE TABLE pumpswap_create_pool_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    pool String,
+    creator LowCardinality(String) CODEC(LZ4),
+    index UInt16,
+    base_mint LowCardinality(String) CODEC(LZ4),
+    quote_mint LowCardinality(String) CODEC(LZ4),
+    lp_mint LowCardinality(String) CODEC(LZ4),
+    base_amount_in UInt64,
+    quote_amount_in UInt64,
+    lp_token_amount_out UInt64,
+    PROJECTION projection_base_mint (SELECT * ORDER BY base_mint, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_creator (SELECT * ORDER BY creator, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE pumpswap_swap_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    user LowCardinality(String) CODEC(LZ4),
+    pool LowCardinality(String) CODEC(LZ4),
+    base_mint LowCardinality(String) CODEC(LZ4),
+    quote_mint LowCardinality(String) CODEC(LZ4),
+    direction String CODEC(LZ4),
+    base_amount UInt64,
+    quote_amount UInt64,
+    ui_base_amount Nullable(Decimal(38, 18)),
+    ui_quote_amount Nullable(Decimal(38, 18)),
+    usd_value Nullable(Float64),
+    pool_base_token_reserves UInt64,
+    pool_quote_token_reserves UInt64,
+    lp_fee UInt64,
+    protocol_fee UInt64,
+    coin_creator LowCardinality(String) CODEC(LZ4),
+    coin_creator_fee Nullable(UInt64),
+    PROJECTION projection_base_mint (SELECT * ORDER BY base_mint, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_pool (SELECT * ORDER BY pool, slot, transaction_index, instruction_index),
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PARTITION BY toInt64(slot / 1e6)
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE pumpswap_deposit_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    user LowCardinality(String) CODEC(LZ4),
+    pool LowCardinality(String) CODEC(LZ4),
+    base_mint LowCardinality(String) CODEC(LZ4),
+    quote_mint LowCardinality(String) CODEC(LZ4),
+    lp_token_amount UInt64,
+    base_amount UInt64,
+    quote_amount UInt64,
+    pool_base_token_reserves UInt64,
+    pool_quote_token_reserves UInt64,
+    lp_mint_supply UInt64,
+    PROJECTION projection_pool (SELECT * ORDER BY pool, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
+CREATE TABLE pumpswap_withdraw_events
+(
+    slot UInt64,
+    transaction_index UInt64,
+    instruction_index UInt64,
+    partial_signature String,
+    partial_blockhash String,
+    user LowCardinality(String) CODEC(LZ4),
+    pool LowCardinality(String) CODEC(LZ4),
+    base_mint LowCardinality(String) CODEC(LZ4),
+    quote_mint LowCardinality(String) CODEC(LZ4),
+    lp_token_amount UInt64,
+    base_amount UInt64,
+    quote_amount UInt64,
+    pool_base_token_reserves UInt64,
+    pool_quote_token_reserves UInt64,
+    lp_mint_supply UInt64,
+    PROJECTION projection_pool (SELECT * ORDER BY pool, slot, transaction_index, instruction_index), -- RECOMMENDED
+    PROJECTION projection_user (SELECT * ORDER BY user, slot, transaction_index, instruction_index), -- RECOMMENDED
+    parent_instruction_index Int64 DEFAULT -1,
+    top_instruction_index Int64 DEFAULT -1,
+    parent_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+    top_instruction_program_id LowCardinality(String) DEFAULT '' CODEC(LZ4),
+)
+ENGINE = MergeTree
+PRIMARY KEY (slot, transaction_index, instruction_index)
+ORDER BY (slot, transaction_index, instruction_index);
+
 -- MPL TOKEN METADATA EVENTS
 
 CREATE TABLE mpl_token_metadata_create_metadata_account_v3_events
@@ -0,0 +1,108 @@
+use anyhow::Error;
+
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+use substreams_solana_utils::pubkey::Pubkey;
+
+use crate::decoding::{get_account, Reader};
+use crate::instruction::IndexedInstruction;
+
+/// AddressLookupTab1e1111111111111111111111111
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: Pubkey = Pubkey([2, 119, 166, 175, 151, 51, 155, 122, 200, 141, 24, 146, 201, 4, 70, 245, 0, 2, 48, 146, 102, 246, 46, 83, 193, 24, 36, 73, 130, 0, 0, 0]);
+
+const CREATE_LOOKUP_TABLE_TAG: u32 = 0;
+const FREEZE_LOOKUP_TABLE_TAG: u32 = 1;
+const EXTEND_LOOKUP_TABLE_TAG: u32 = 2;
+const DEACTIVATE_LOOKUP_TABLE_TAG: u32 = 3;
+const CLOSE_LOOKUP_TABLE_TAG: u32 = 4;
+
+/// An instruction of the Address Lookup Table program.
+///
+/// The payer is only set for the instructions which fund the table, and the recipient only when closing it.
+#[derive(Debug)]
+pub struct LookupTableEvent {
+    pub instruction_type: &'static str,
+    pub lookup_table: String,
+    pub authority: String,
+    pub payer: Option<String>,
+    pub recipient: Option<String>,
+    pub recent_slot: Option<u64>,
+    pub new_addresses: Vec<String>,
+}
+
+/// A lookup table used by a v0 transaction, with the indexes it loads and the addresses at them.
+#[derive(Debug)]
+pub struct TransactionLookupTable {
+    pub lookup_table: String,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+    pub writable_addresses: Vec<String>,
+    pub readonly_addresses: Vec<String>,
+}
+
+/// Parses an instruction of the Address Lookup Table program, whose data is serialized with bincode.
+pub fn parse_instruction(instruction: &IndexedInstruction) -> Result<Option<LookupTableEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let tag = match reader.read_u32() {
+        Ok(tag) => tag,
+        Err(_) => return Ok(None),
+    };
+    let mut event = LookupTableEvent {
+        instruction_type: "",
+        lookup_table: get_account(instruction, 0)?,
+        authority: get_account(instruction, 1)?,
+        payer: None,
+        recipient: None,
+        recent_slot: None,
+        new_addresses: Vec::new(),
+    };
+    match tag {
+        CREATE_LOOKUP_TABLE_TAG => {
+            event.instruction_type = "create";
+            event.payer = Some(get_account(instruction, 2)?);
+            event.recent_slot = Some(reader.read_u64()?);
+        },
+        FREEZE_LOOKUP_TABLE_TAG => event.instruction_type = "freeze",
+        EXTEND_LOOKUP_TABLE_TAG => {
+            event.instruction_type = "extend";
+            // The payer is only needed when the table must be funded for its new size.
+            event.payer = get_account(instruction, 2).ok();
+            let length = reader.read_u64()?;
+            event.new_addresses = (0..length).map(|_| reader.read_pubkey()).collect::<Result<Vec<_>, _>>()?;
+        },
+        DEACTIVATE_LOOKUP_TABLE_TAG => event.instruction_type = "deactivate",
+        CLOSE_LOOKUP_TABLE_TAG => {
+            event.instruction_type = "close";
+            event.recipient = Some(get_account(instruction, 2)?);
+        },
+        _ => return Ok(None),
+    }
+    Ok(Some(event))
+}
+
+/// Version of the message of `transaction`, `legacy` or `0`.
+pub fn get_version(transaction: &ConfirmedTransaction) -> &'static str {
+    match transaction.transaction.as_ref().unwrap().message.as_ref().unwrap().versioned {
+        true => "0",
+        false => "legacy",
+    }
+}
+
+/// The lookup tables used by `transaction`, in the order of its message.
+///
+/// The runtime loads the writable addresses of every table first, then their readonly addresses, so the
+/// loaded addresses are split between tables by their number of indexes.
+pub fn get_transaction_lookup_tables(transaction: &ConfirmedTransaction) -> Vec<TransactionLookupTable> {
+    let message = transaction.transaction.as_ref().unwrap().message.as_ref().unwrap();
+    let meta = transaction.meta.as_ref().unwrap();
+    let mut writable_addresses = meta.loaded_writable_addresses.iter().map(|x| bs58::encode(x).into_string());
+    let mut readonly_addresses = meta.loaded_readonly_addresses.iter().map(|x| bs58::encode(x).into_string());
+    message.address_table_lookups.iter()
+        .map(|lookup| TransactionLookupTable {
+            lookup_table: bs58::encode(&lookup.account_key).into_string(),
+            writable_indexes: lookup.writable_indexes.clone(),
+            readonly_indexes: lookup.readonly_indexes.clone(),
+            writable_addresses: writable_addresses.by_ref().take(lookup.writable_indexes.len()).collect(),
+            readonly_addresses: readonly_addresses.by_ref().take(lookup.readonly_indexes.len()).collect(),
+        })
+        .collect()
+}
@@ -0,0 +1,66 @@
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::spl_token::constants::TOKEN_PROGRAM_ID;
+
+use crate::decoding::get_account;
+use crate::instruction::IndexedInstruction;
+
+/// ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL
+pub const ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID: Pubkey = Pubkey([140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142, 13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216, 219, 233, 248, 89]);
+
+const CREATE_TAG: u8 = 0;
+const CREATE_IDEMPOTENT_TAG: u8 = 1;
+const RECOVER_NESTED_TAG: u8 = 2;
+
+/// A creation of an associated token account, or a recovery of the tokens of a nested one.
+///
+/// A recovery moves the tokens of `nested_token_account`, the associated token account of `mint` owned by the
+/// associated token account of `owner_mint`, to `associated_token_account`, and closes the nested account. The
+/// destination must already exist.
+#[derive(Debug)]
+pub struct AssociatedTokenAccountEvent {
+    pub instruction_type: &'static str,
+    pub wallet: String,
+    pub mint: String,
+    pub associated_token_account: String,
+    pub payer: Option<String>,
+    pub token_program: String,
+    pub already_existed: bool,
+    pub nested_token_account: Option<String>,
+    pub owner_mint: Option<String>,
+}
+
+pub fn parse_instruction(instruction: &IndexedInstruction) -> Result<Option<AssociatedTokenAccountEvent>, Error> {
+    // The first version of the program took no instruction data to create an account.
+    let tag = instruction.data().first().copied().unwrap_or(CREATE_TAG);
+    let event = match tag {
+        CREATE_TAG | CREATE_IDEMPOTENT_TAG => AssociatedTokenAccountEvent {
+            instruction_type: if tag == CREATE_TAG { "create" } else { "create_idempotent" },
+            payer: Some(get_account(instruction, 0)?),
+            associated_token_account: get_account(instruction, 1)?,
+            wallet: get_account(instruction, 2)?,
+            mint: get_account(instruction, 3)?,
+            // Before the token program account was required, accounts were always created by the token program.
+            token_program: get_account(instruction, 5).unwrap_or_else(|_| TOKEN_PROGRAM_ID.to_string()),
+            // The program only invokes others to create the account, so an idempotent creation without inner
+            // instructions found it already created.
+            already_existed: instruction.inner_instructions().is_empty(),
+            nested_token_account: None,
+            owner_mint: None,
+        },
+        RECOVER_NESTED_TAG => AssociatedTokenAccountEvent {
+            instruction_type: "recover_nested",
+            nested_token_account: Some(get_account(instruction, 0)?),
+            mint: get_account(instruction, 1)?,
+            associated_token_account: get_account(instruction, 2)?,
+            owner_mint: Some(get_account(instruction, 4)?),
+            wallet: get_account(instruction, 5)?,
+            token_program: get_account(instruction, 6)?,
+            payer: None,
+            already_existed: true,
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
@@ -0,0 +1,105 @@
+use std::collections::{BTreeMap, HashMap};
+
+use substreams::store::{DeltaString, Deltas, StoreNew, StoreSet, StoreSetString};
+use substreams_database_change::tables::Tables;
+use substreams_solana::pb::sf::solana::r#type::v1::{Block, ConfirmedTransaction, TokenBalance};
+
+/// Keeps the balance of every token account, one key per field: `{account}:{field}`.
+///
+/// Fields are `owner`, `mint` and `balance`, the absolute balance at the end of the last transaction which
+/// changed the account. Balances are taken from the transaction meta token balances, so that every instruction
+/// moving tokens (transfers, mints, burns, closes, ...) is accounted for, and closed accounts get a zero balance.
+#[substreams::handlers::store]
+fn store_token_account_balances(block: Block, store: StoreSetString) {
+    for (index, transaction) in block.transactions.iter().enumerate() {
+        for (account, owner, mint, balance) in get_token_account_balance_changes(transaction) {
+            store.set(index as u64, format!("{account}:owner"), &owner);
+            store.set(index as u64, format!("{account}:mint"), &mint);
+            store.set(index as u64, format!("{account}:balance"), &balance.to_string());
+        }
+    }
+}
+
+/// The token accounts whose owner or balance changed in `transaction`, with their owner, mint and balance at its end.
+fn get_token_account_balance_changes(transaction: &ConfirmedTransaction) -> Vec<(String, String, String, u64)> {
+    let meta = transaction.meta.as_ref().unwrap();
+    if meta.err.is_some() {
+        return Vec::new();
+    }
+
+    let accounts = get_account_keys(transaction);
+    let pre_balances = get_token_balances_by_account(&meta.pre_token_balances);
+    let mut post_balances = get_token_balances_by_account(&meta.post_token_balances);
+    // Closed accounts have no post token balance.
+    for (account_index, (owner, mint, _)) in pre_balances.iter() {
+        post_balances.entry(*account_index).or_insert_with(|| (owner.clone(), mint.clone(), 0));
+    }
+    post_balances.into_iter()
+        .filter(|(account_index, post_balance)| pre_balances.get(account_index) != Some(post_balance))
+        .filter_map(|(account_index, (owner, mint, balance))| {
+            let account = accounts.get(account_index as usize)?;
+            Some((bs58::encode(account).into_string(), owner, mint, balance))
+        })
+        .collect()
+}
+
+fn get_token_balances_by_account(token_balances: &[TokenBalance]) -> HashMap<u32, (String, String, u64)> {
+    token_balances.iter().map(|token_balance| {
+        let amount = token_balance.ui_token_amount.as_ref().map(|x| x.amount.parse::<u64>().unwrap()).unwrap_or(0);
+        (token_balance.account_index, (token_balance.owner.clone(), token_balance.mint.clone(), amount))
+    }).collect()
+}
+
+/// The accounts token balances refer to by index, i.e. the message keys followed by the loaded addresses.
+fn get_account_keys(transaction: &ConfirmedTransaction) -> Vec<&Vec<u8>> {
+    let message = transaction.transaction.as_ref().unwrap().message.as_ref().unwrap();
+    let meta = transaction.meta.as_ref().unwrap();
+    message.account_keys.iter()
+        .chain(meta.loaded_writable_addresses.iter())
+        .chain(meta.loaded_readonly_addresses.iter())
+        .collect()
+}
+
+/// Balances and mints of the token accounts of a transaction at its end, keyed by account address.
+pub struct PostTokenBalances(HashMap<String, (String, u64)>);
+
+impl PostTokenBalances {
+    pub fn new(transaction: &ConfirmedTransaction) -> Self {
+        let meta = transaction.meta.as_ref().unwrap();
+        let accounts = get_account_keys(transaction);
+        let balances = meta.post_token_balances.iter()
+            .filter_map(|token_balance| {
+                let account = accounts.get(token_balance.account_index as usize)?;
+                let amount = token_balance.ui_token_amount.as_ref().map(|x| x.amount.parse::<u64>().unwrap()).unwrap_or(0);
+                Some((bs58::encode(account).into_string(), (token_balance.mint.clone(), amount)))
+            })
+            .collect();
+        PostTokenBalances(balances)
+    }
+
+    pub fn get(&self, account: &str) -> Option<u64> {
+        self.0.get(account).map(|(_, amount)| *amount)
+    }
+
+    pub fn get_mint(&self, account: &str) -> Option<String> {
+        self.0.get(account).map(|(mint, _)| mint.clone())
+    }
+}
+
+/// Upserts the token accounts changed by the block into the `token_holder_balances` table.
+///
+/// Every change sets all the fields of an account, so the deltas of the block hold the whole account.
+pub fn insert_token_holder_balances(deltas: &Deltas<DeltaString>, slot: u64, tables: &mut Tables) {
+    let mut accounts: BTreeMap<&str, HashMap<&str, &str>> = BTreeMap::new();
+    for delta in deltas.deltas.iter() {
+        let (account, field) = delta.key.split_once(':').unwrap();
+        accounts.entry(account).or_default().insert(field, &delta.new_value);
+    }
+    for (account, fields) in accounts {
+        tables.create_row("token_holder_balances", account.to_string())
+            .set("owner", fields["owner"])
+            .set("mint", fields["mint"])
+            .set("balance", fields["balance"])
+            .set("slot", slot);
+    }
+}
@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Context, Error};
+
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use substreams_solana_utils::transaction::{get_context, TransactionContext};
+use substreams_solana_utils::spl_token::constants::TOKEN_PROGRAM_ID;
+
+use raydium_amm_substream::raydium_amm::constants::RAYDIUM_AMM_PROGRAM_ID;
+use raydium_amm_substream::pb::raydium_amm::raydium_amm_event;
+use spl_token_substream::pb::spl_token::{spl_token_event, AuthorityType};
+use mpl_token_metadata_substream::mpl_token_metadata::constants::MPL_TOKEN_METADATA_PROGRAM_ID;
+use mpl_token_metadata_substream::pb::mpl_token_metadata::mpl_token_metadata_event;
+use pumpfun_substream::pumpfun::PUMPFUN_PROGRAM_ID;
+use pumpfun_substream::pb::pumpfun::pumpfun_event;
+
+use crate::balances::PostTokenBalances;
+use crate::decoding::get_account;
+use crate::instruction::{get_indexed_instructions, IndexedInstruction, IndexedInstructions};
+use crate::order_books::{self, LotSizes, OrderBookEvent};
+use crate::pb::block_events::{block_event::Event, BlockEvent, BlockEvents};
+use crate::pb::block_events::{Burn, InitializeMint, MintMetadata, MintTo, OrderBookMarket, RaydiumAmmPool, SetMintAuthority};
+use crate::pb::block_events::{PumpfunComplete, PumpfunCreate, PumpfunSetParams, PumpfunSwap, PumpswapMigration};
+use crate::pumpfun_fees::get_transferred_fee;
+use crate::pumpswap::{self, PumpswapEvent, PUMPSWAP_PROGRAM_ID};
+
+/// Parses the events the stores are built from, so that each store doesn't parse the whole block again.
+///
+/// Stores use the position of an event in the block as its ordinal.
+#[substreams::handlers::map]
+fn map_block_events(block: Block) -> Result<BlockEvents, Error> {
+    let mut events: Vec<BlockEvent> = Vec::new();
+    for (index, transaction) in block.transactions.iter().enumerate() {
+        if transaction.meta.as_ref().unwrap().err.is_some() {
+            continue;
+        }
+        let instructions = get_indexed_instructions(transaction)?;
+        let mut context = get_context(transaction)?;
+        let post_token_balances = PostTokenBalances::new(transaction);
+        for instruction in instructions.flattened().iter() {
+            context.update_balance(&instruction.instruction.instruction);
+            if let Some(event) = parse_instruction(instruction, &context, &post_token_balances).with_context(|| format!("Transaction {}", context.signature))? {
+                events.push(BlockEvent {
+                    transaction_index: index as u32,
+                    instruction_index: instruction.index,
+                    event: Some(event),
+                });
+            }
+        }
+    }
+    Ok(BlockEvents { events })
+}
+
+fn parse_instruction(instruction: &IndexedInstruction, context: &TransactionContext, post_token_balances: &PostTokenBalances) -> Result<Option<Event>, Error> {
+    let program_id = instruction.program_id();
+    let event = if program_id == TOKEN_PROGRAM_ID {
+        match spl_token_substream::parse_instruction(&instruction.instruction, context)? {
+            Some(spl_token_event::Event::InitializeMint(initialize_mint)) => Some(Event::InitializeMint(InitializeMint {
+                mint: initialize_mint.mint,
+                decimals: initialize_mint.decimals,
+                mint_authority: initialize_mint.mint_authority,
+                freeze_authority: initialize_mint.freeze_authority,
+            })),
+            Some(spl_token_event::Event::SetAuthority(set_authority)) => {
+                let authority_type = match AuthorityType::from_i32(set_authority.authority_type).unwrap() {
+                    AuthorityType::MintTokens => "mint_tokens",
+                    AuthorityType::FreezeAccount => "freeze_account",
+                    _ => return Ok(None),
+                };
+                Some(Event::SetMintAuthority(SetMintAuthority {
+                    mint: set_authority.mint,
+                    authority_type: authority_type.to_string(),
+                    new_authority: set_authority.new_authority,
+                }))
+            },
+            Some(spl_token_event::Event::MintTo(mint_to)) => Some(Event::MintTo(MintTo {
+                mint: mint_to.mint,
+                amount: mint_to.amount,
+            })),
+            Some(spl_token_event::Event::Burn(burn)) => Some(Event::Burn(Burn {
+                mint: burn.source.unwrap().mint,
+                amount: burn.amount,
+            })),
+            _ => None,
+        }
+    } else if program_id == MPL_TOKEN_METADATA_PROGRAM_ID {
+        match mpl_token_metadata_substream::parse_instruction(&instruction.instruction, context).map_err(|x| anyhow!(x))? {
+            Some(mpl_token_metadata_event::Event::CreateMetadataAccountV3(create_metadata_account_v3)) => {
+                let data = create_metadata_account_v3.data.unwrap();
+                Some(Event::MintMetadata(MintMetadata {
+                    mint: create_metadata_account_v3.mint,
+                    name: data.name,
+                    symbol: data.symbol,
+                    uri: data.uri,
+                }))
+            },
+            _ => None,
+        }
+    } else if program_id == RAYDIUM_AMM_PROGRAM_ID {
+        parse_raydium_amm_instruction(instruction, context, post_token_balances)?
+    } else if program_id == PUMPFUN_PROGRAM_ID {
+        match pumpfun_substream::parse_instruction(&instruction.instruction, context)? {
+            Some(pumpfun_event::Event::Create(create)) => Some(Event::PumpfunCreate(PumpfunCreate {
+                mint: create.mint,
+                user: create.user,
+                bonding_curve: create.bonding_curve,
+                name: create.name,
+                symbol: create.symbol,
+                uri: create.uri,
+            })),
+            Some(pumpfun_event::Event::Swap(swap)) => Some(Event::PumpfunSwap(PumpfunSwap {
+                mint: swap.mint,
+                sol_amount: swap.sol_amount.unwrap_or(0),
+                virtual_sol_reserves: swap.virtual_sol_reserves,
+                virtual_token_reserves: swap.virtual_token_reserves,
+                real_sol_reserves: swap.real_sol_reserves,
+                real_token_reserves: swap.real_token_reserves,
+                transferred_fee_lamports: get_transferred_fee(instruction, context),
+            })),
+            Some(pumpfun_event::Event::Withdraw(withdraw)) => Some(Event::PumpfunComplete(PumpfunComplete {
+                mint: withdraw.mint,
+            })),
+            Some(pumpfun_event::Event::SetParams(set_params)) => Some(Event::PumpfunSetParams(PumpfunSetParams {
+                fee_basis_points: set_params.fee_basis_points,
+            })),
+            _ => None,
+        }
+    } else if program_id == PUMPSWAP_PROGRAM_ID {
+        let is_migration = instruction.parent_instruction().map_or(false, |parent_instruction| parent_instruction.program_id() == PUMPFUN_PROGRAM_ID);
+        match pumpswap::parse_instruction(instruction)? {
+            Some(PumpswapEvent::CreatePool(create_pool)) if is_migration => Some(Event::PumpswapMigration(PumpswapMigration {
+                base_mint: create_pool.base_mint,
+                pool: create_pool.pool,
+            })),
+            _ => None,
+        }
+    } else {
+        match order_books::parse_instruction(instruction, context)? {
+            Some(OrderBookEvent::CreateMarket(create_market)) => {
+                let mut market = OrderBookMarket {
+                    market: create_market.market,
+                    base_mint: create_market.base_mint,
+                    quote_mint: create_market.quote_mint,
+                    ..Default::default()
+                };
+                match create_market.lot_sizes {
+                    LotSizes::OpenbookV2 { base_lot_size, quote_lot_size } => {
+                        market.program = "openbook_v2".to_string();
+                        market.base_lot_size = Some(base_lot_size);
+                        market.quote_lot_size = Some(quote_lot_size);
+                    },
+                    LotSizes::Phoenix { tick_size_in_quote_lots_per_base_unit, num_base_lots_per_base_unit, num_quote_lots_per_quote_unit, raw_base_units_per_base_unit } => {
+                        market.program = "phoenix".to_string();
+                        market.tick_size_in_quote_lots_per_base_unit = Some(tick_size_in_quote_lots_per_base_unit);
+                        market.num_base_lots_per_base_unit = Some(num_base_lots_per_base_unit);
+                        market.num_quote_lots_per_quote_unit = Some(num_quote_lots_per_quote_unit);
+                        market.raw_base_units_per_base_unit = Some(raw_base_units_per_base_unit);
+                    },
+                }
+                Some(Event::OrderBookMarket(market))
+            },
+            _ => None,
+        }
+    };
+    Ok(event)
+}
+
+fn parse_raydium_amm_instruction(instruction: &IndexedInstruction, context: &TransactionContext, post_token_balances: &PostTokenBalances) -> Result<Option<Event>, Error> {
+    let pool = match raydium_amm_substream::parse_instruction(&instruction.instruction, context).map_err(|x| anyhow!(x))? {
+        Some(raydium_amm_event::Event::Initialize(initialize)) => RaydiumAmmPool {
+            instruction_type: "initialize".to_string(),
+            amm: initialize.amm,
+            coin_mint: initialize.coin_mint,
+            pc_mint: initialize.pc_mint,
+            lp_mint: initialize.lp_mint,
+            creator: initialize.user,
+            coin_vault: get_account(instruction, 10)?,
+            pc_vault: get_account(instruction, 11)?,
+            ..Default::default()
+        },
+        Some(raydium_amm_event::Event::Deposit(deposit)) => RaydiumAmmPool {
+            instruction_type: "deposit".to_string(),
+            amm: deposit.amm,
+            coin_mint: deposit.coin_mint,
+            pc_mint: deposit.pc_mint,
+            lp_mint: deposit.lp_mint,
+            pool_coin_amount: deposit.pool_coin_amount,
+            pool_pc_amount: deposit.pool_pc_amount,
+            coin_vault: get_account(instruction, 6)?,
+            pc_vault: get_account(instruction, 7)?,
+            ..Default::default()
+        },
+        Some(raydium_amm_event::Event::Withdraw(withdraw)) => RaydiumAmmPool {
+            instruction_type: "withdraw".to_string(),
+            amm: withdraw.amm,
+            coin_mint: withdraw.coin_mint,
+            pc_mint: withdraw.pc_mint,
+            lp_mint: withdraw.lp_mint,
+            pool_coin_amount: withdraw.pool_coin_amount,
+            pool_pc_amount: withdraw.pool_pc_amount,
+            coin_vault: get_account(instruction, 6)?,
+            pc_vault: get_account(instruction, 7)?,
+            ..Default::default()
+        },
+        Some(raydium_amm_event::Event::Swap(swap)) => {
+            // The AMM target orders account was dropped from the accounts of swaps at some point.
+            let vault_index = match instruction.accounts().len() {
+                17 => 4,
+                _ => 5,
+            };
+            let coin_vault = get_account(instruction, vault_index)?;
+            let pc_vault = get_account(instruction, vault_index + 1)?;
+            RaydiumAmmPool {
+                instruction_type: "swap".to_string(),
+                amm: swap.amm,
+                coin_mint: post_token_balances.get_mint(&coin_vault).unwrap_or_default(),
+                pc_mint: post_token_balances.get_mint(&pc_vault).unwrap_or_default(),
+                pool_coin_amount: swap.pool_coin_amount,
+                pool_pc_amount: swap.pool_pc_amount,
+                coin_vault,
+                pc_vault,
+                ..Default::default()
+            }
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(Event::RaydiumAmmPool(pool)))
+}
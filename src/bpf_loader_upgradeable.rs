@@ -0,0 +1,159 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::Pubkey;
+
+use crate::decoding::{get_account, Reader};
+use crate::instruction::IndexedInstruction;
+
+/// BPFLoaderUpgradeab1e11111111111111111111111
+pub const BPF_LOADER_UPGRADEABLE_PROGRAM_ID: Pubkey = Pubkey([2, 168, 246, 145, 78, 136, 161, 176, 226, 16, 21, 62, 247, 99, 174, 43, 0, 194, 185, 61, 22, 193, 36, 210, 192, 83, 122, 16, 4, 128, 0, 0]);
+
+const INITIALIZE_BUFFER_TAG: u32 = 0;
+const WRITE_TAG: u32 = 1;
+const DEPLOY_WITH_MAX_DATA_LEN_TAG: u32 = 2;
+const UPGRADE_TAG: u32 = 3;
+const SET_AUTHORITY_TAG: u32 = 4;
+const CLOSE_TAG: u32 = 5;
+const EXTEND_PROGRAM_TAG: u32 = 6;
+const SET_AUTHORITY_CHECKED_TAG: u32 = 7;
+
+/// An instruction of the upgradeable BPF loader, with the accounts it involves.
+///
+/// `account` is the buffer or the programdata account changed by `set_authority` and `close`, which can't be
+/// told apart from the instruction alone, except when a program is closed. The data length is the size written
+/// by `write`, the maximum size of a deployed program and the size added by `extend_program`.
+///
+/// Programs are written to their buffer a chunk at a time, so writes are aggregated by `BufferWrites`.
+#[derive(Debug, Default)]
+pub struct ProgramDeployEvent {
+    pub instruction_type: &'static str,
+    pub program_id: Option<String>,
+    pub programdata: Option<String>,
+    pub buffer: Option<String>,
+    pub account: Option<String>,
+    pub authority: Option<String>,
+    pub new_authority: Option<String>,
+    pub payer: Option<String>,
+    pub recipient: Option<String>,
+    pub data_length: Option<u64>,
+    pub write_offset: Option<u32>,
+}
+
+/// Parses an instruction of the upgradeable BPF loader, whose data is serialized with bincode.
+pub fn parse_instruction(instruction: &IndexedInstruction) -> Result<Option<ProgramDeployEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let tag = match reader.read_u32() {
+        Ok(tag) => tag,
+        Err(_) => return Ok(None),
+    };
+    let event = match tag {
+        INITIALIZE_BUFFER_TAG => ProgramDeployEvent {
+            instruction_type: "initialize_buffer",
+            buffer: Some(get_account(instruction, 0)?),
+            authority: get_account(instruction, 1).ok(),
+            ..Default::default()
+        },
+        WRITE_TAG => {
+            let write_offset = reader.read_u32()?;
+            ProgramDeployEvent {
+                instruction_type: "write",
+                buffer: Some(get_account(instruction, 0)?),
+                authority: Some(get_account(instruction, 1)?),
+                data_length: Some(reader.read_u64()?),
+                write_offset: Some(write_offset),
+                ..Default::default()
+            }
+        },
+        DEPLOY_WITH_MAX_DATA_LEN_TAG => ProgramDeployEvent {
+            instruction_type: "deploy_with_max_data_len",
+            payer: Some(get_account(instruction, 0)?),
+            programdata: Some(get_account(instruction, 1)?),
+            program_id: Some(get_account(instruction, 2)?),
+            buffer: Some(get_account(instruction, 3)?),
+            authority: Some(get_account(instruction, 7)?),
+            data_length: Some(reader.read_u64()?),
+            ..Default::default()
+        },
+        UPGRADE_TAG => ProgramDeployEvent {
+            instruction_type: "upgrade",
+            programdata: Some(get_account(instruction, 0)?),
+            program_id: Some(get_account(instruction, 1)?),
+            buffer: Some(get_account(instruction, 2)?),
+            recipient: Some(get_account(instruction, 3)?),
+            authority: Some(get_account(instruction, 6)?),
+            ..Default::default()
+        },
+        SET_AUTHORITY_TAG | SET_AUTHORITY_CHECKED_TAG => ProgramDeployEvent {
+            instruction_type: if tag == SET_AUTHORITY_TAG { "set_authority" } else { "set_authority_checked" },
+            account: Some(get_account(instruction, 0)?),
+            authority: Some(get_account(instruction, 1)?),
+            // Without a new authority, the program becomes immutable.
+            new_authority: get_account(instruction, 2).ok(),
+            ..Default::default()
+        },
+        CLOSE_TAG => {
+            let program_id = get_account(instruction, 3).ok();
+            let account = get_account(instruction, 0)?;
+            ProgramDeployEvent {
+                instruction_type: "close",
+                programdata: program_id.as_ref().map(|_| account.clone()),
+                program_id,
+                account: Some(account),
+                recipient: Some(get_account(instruction, 1)?),
+                authority: get_account(instruction, 2).ok(),
+                ..Default::default()
+            }
+        },
+        EXTEND_PROGRAM_TAG => ProgramDeployEvent {
+            instruction_type: "extend_program",
+            programdata: Some(get_account(instruction, 0)?),
+            program_id: Some(get_account(instruction, 1)?),
+            payer: get_account(instruction, 3).ok(),
+            data_length: Some(reader.read_u32()? as u64),
+            ..Default::default()
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+/// The writes of a transaction to a buffer, from its first write.
+#[derive(Debug)]
+pub struct BufferWrite<'a> {
+    pub instruction: Rc<IndexedInstruction<'a>>,
+    pub buffer: String,
+    pub authority: String,
+    pub write_count: u32,
+    pub data_length: u64,
+    pub write_offset: u32,
+}
+
+/// Aggregates the writes of a transaction by buffer, with their total size and lowest offset.
+#[derive(Debug, Default)]
+pub struct BufferWrites<'a>(pub Vec<BufferWrite<'a>>);
+
+impl<'a> BufferWrites<'a> {
+    pub fn add(&mut self, instruction: &Rc<IndexedInstruction<'a>>, event: ProgramDeployEvent) {
+        if event.instruction_type != "write" {
+            return;
+        }
+        let (buffer, authority, data_length, write_offset) = (event.buffer.unwrap(), event.authority.unwrap(), event.data_length.unwrap(), event.write_offset.unwrap());
+        match self.0.iter_mut().find(|x| x.buffer == buffer) {
+            Some(write) => {
+                write.write_count += 1;
+                write.data_length += data_length;
+                write.write_offset = write.write_offset.min(write_offset);
+            },
+            None => self.0.push(BufferWrite {
+                instruction: Rc::clone(instruction),
+                buffer,
+                authority,
+                write_count: 1,
+                data_length,
+                write_offset,
+            }),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::Clock;
+use substreams::store::{
+    DeltaFloat64, DeltaInt64, Deltas, StoreAdd, StoreAddFloat64, StoreDelete, StoreGet, StoreGetFloat64, StoreMax,
+    StoreMaxFloat64, StoreMin, StoreMinFloat64, StoreNew, StoreSet, StoreSetFloat64, StoreSetIfNotExists,
+    StoreSetIfNotExistsFloat64, StoreSetInt64,
+};
+use substreams_database_change::tables::Tables;
+
+use crate::dex_trades::{get_base_ui_amount, get_quote_ui_amount};
+use crate::pb::dex_trades::{DexTrade, DexTrades};
+
+/// Candle intervals, with their table suffix and length in seconds.
+const INTERVALS: [(&str, i64); 3] = [("1m", 60), ("1h", 3600), ("1d", 86400)];
+
+const MARKET_TYPES: [&str; 2] = ["pool", "mint"];
+
+const TIMESTAMP_KEY: &str = "timestamp";
+
+/// A candle of one market during one interval.
+///
+/// Markets are either a pool, or a base mint across all pools quoted in the same mint.
+struct Candle {
+    interval: &'static str,
+    market_type: &'static str,
+    market: String,
+    quote_mint: String,
+    timestamp: i64,
+}
+
+impl Candle {
+    /// Keys start with the interval and its start, so that the candles of an interval are deleted together.
+    fn key(&self) -> String {
+        format!("{}:{}:{}:{}:{}", self.interval, self.timestamp, self.market_type, self.market, self.quote_mint)
+    }
+
+    fn from_key(key: &str) -> Candle {
+        let parts: Vec<&str> = key.splitn(5, ':').collect();
+        Candle {
+            interval: INTERVALS.into_iter().map(|(interval, _)| interval).find(|x| *x == parts[0]).unwrap(),
+            market_type: MARKET_TYPES.into_iter().find(|x| *x == parts[2]).unwrap(),
+            market: parts[3].to_string(),
+            quote_mint: parts[4].to_string(),
+            timestamp: parts[1].parse().unwrap(),
+        }
+    }
+}
+
+/// Returns the candles a trade at `timestamp` contributes to.
+fn get_candles(trade: &DexTrade, timestamp: i64) -> Vec<Candle> {
+    let mut candles = Vec::new();
+    for (interval, seconds) in INTERVALS {
+        let timestamp = timestamp - timestamp.rem_euclid(seconds);
+        for (market_type, market) in MARKET_TYPES.into_iter().zip([&trade.pool, &trade.base_mint]) {
+            candles.push(Candle {
+                interval,
+                market_type,
+                market: market.clone(),
+                quote_mint: trade.quote_mint.clone(),
+                timestamp,
+            });
+        }
+    }
+    candles
+}
+
+/// Keeps the timestamp of the last block under the `timestamp` key, so that its deltas give the previous one.
+#[substreams::handlers::store]
+fn store_block_timestamp(clock: Clock, store: StoreSetInt64) {
+    store.set(0, TIMESTAMP_KEY, &clock.timestamp.unwrap().seconds);
+}
+
+/// Keeps the timestamp of the block before the last one under the `timestamp` key.
+#[substreams::handlers::store]
+fn store_previous_block_timestamp(block_timestamp_deltas: Deltas<DeltaInt64>, store: StoreSetInt64) {
+    for delta in block_timestamp_deltas.deltas.iter() {
+        store.set(0, TIMESTAMP_KEY, &delta.old_value);
+    }
+}
+
+/// Deletes the candles of every interval from the one of the old timestamp of `timestamp_deltas` up to the one
+/// of its new timestamp, excluded, so that intervals without any block are deleted too.
+fn delete_candles<S: StoreDelete>(store: &S, timestamp_deltas: &Deltas<DeltaInt64>) {
+    for delta in timestamp_deltas.deltas.iter() {
+        // Nothing was stored before the first block.
+        if delta.old_value == 0 {
+            continue;
+        }
+        for (interval, seconds) in INTERVALS {
+            let mut start = delta.old_value - delta.old_value.rem_euclid(seconds);
+            let end = delta.new_value - delta.new_value.rem_euclid(seconds);
+            while start < end {
+                store.delete_prefix(0, &format!("{interval}:{start}:"));
+                start += seconds;
+            }
+        }
+    }
+}
+
+/// Trades which can be charted, i.e. whose price is known.
+fn get_priced_trades(dex_trades: &DexTrades) -> impl Iterator<Item = (u64, &DexTrade, f64)> {
+    dex_trades.trades.iter().enumerate().filter_map(|(ordinal, trade)| trade.price.map(|price| (ordinal as u64, trade, price)))
+}
+
+/// `store_candle_closes` deletes the candles of an interval on the first block after it, so that `insert_candles`
+/// can tell which candles closed. The other candle stores delete them one block later, once they were written.
+#[substreams::handlers::store]
+fn store_candle_opens(clock: Clock, dex_trades: DexTrades, previous_block_timestamp_deltas: Deltas<DeltaInt64>, store: StoreSetIfNotExistsFloat64) {
+    let timestamp = clock.timestamp.unwrap().seconds;
+    delete_candles(&store, &previous_block_timestamp_deltas);
+    for (ordinal, trade, price) in get_priced_trades(&dex_trades) {
+        for candle in get_candles(trade, timestamp) {
+            store.set_if_not_exists(ordinal, candle.key(), &price);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_candle_highs(clock: Clock, dex_trades: DexTrades, previous_block_timestamp_deltas: Deltas<DeltaInt64>, store: StoreMaxFloat64) {
+    let timestamp = clock.timestamp.unwrap().seconds;
+    delete_candles(&store, &previous_block_timestamp_deltas);
+    for (ordinal, trade, price) in get_priced_trades(&dex_trades) {
+        for candle in get_candles(trade, timestamp) {
+            store.max(ordinal, candle.key(), price);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_candle_lows(clock: Clock, dex_trades: DexTrades, previous_block_timestamp_deltas: Deltas<DeltaInt64>, store: StoreMinFloat64) {
+    let timestamp = clock.timestamp.unwrap().seconds;
+    delete_candles(&store, &previous_block_timestamp_deltas);
+    for (ordinal, trade, price) in get_priced_trades(&dex_trades) {
+        for candle in get_candles(trade, timestamp) {
+            store.min(ordinal, candle.key(), price);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_candle_closes(clock: Clock, dex_trades: DexTrades, block_timestamp_deltas: Deltas<DeltaInt64>, store: StoreSetFloat64) {
+    let timestamp = clock.timestamp.unwrap().seconds;
+    delete_candles(&store, &block_timestamp_deltas);
+    for (ordinal, trade, price) in get_priced_trades(&dex_trades) {
+        for candle in get_candles(trade, timestamp) {
+            store.set(ordinal, candle.key(), &price);
+        }
+    }
+}
+
+/// Keeps the base and quote volumes of every candle, keyed by `{candle}:base` and `{candle}:quote`.
+#[substreams::handlers::store]
+fn store_candle_volumes(clock: Clock, dex_trades: DexTrades, previous_block_timestamp_deltas: Deltas<DeltaInt64>, store: StoreAddFloat64) {
+    let timestamp = clock.timestamp.unwrap().seconds;
+    delete_candles(&store, &previous_block_timestamp_deltas);
+    for (ordinal, trade, _) in get_priced_trades(&dex_trades) {
+        let base_volume = get_base_ui_amount(trade).unwrap();
+        let quote_volume = get_quote_ui_amount(trade).unwrap();
+        for candle in get_candles(trade, timestamp) {
+            store.add(ordinal, format!("{}:base", candle.key()), base_volume);
+            store.add(ordinal, format!("{}:quote", candle.key()), quote_volume);
+        }
+    }
+}
+
+pub struct CandleStores {
+    pub opens: StoreGetFloat64,
+    pub highs: StoreGetFloat64,
+    pub lows: StoreGetFloat64,
+    pub close_deltas: Deltas<DeltaFloat64>,
+    pub volumes: StoreGetFloat64,
+}
+
+/// Upserts every candle touched by the block's trades into the `candles_*` tables, and the candles whose
+/// interval ended before the block, with `is_closed` set.
+///
+/// A candle row is rewritten on every block that trades in its interval, until the first block of the next
+/// interval writes it one last time as closed.
+pub fn insert_candles(dex_trades: &DexTrades, timestamp: i64, slot: u64, stores: &CandleStores, tables: &mut Tables) {
+    let mut closes: HashMap<&str, f64> = HashMap::new();
+    for delta in stores.close_deltas.deltas.iter() {
+        if delta.operation == Operation::Delete {
+            insert_candle(&Candle::from_key(&delta.key), delta.old_value, true, slot, stores, tables);
+        } else {
+            closes.insert(&delta.key, delta.new_value);
+        }
+    }
+    for (_, trade, _) in get_priced_trades(dex_trades) {
+        for candle in get_candles(trade, timestamp) {
+            let close = closes[candle.key().as_str()];
+            insert_candle(&candle, close, false, slot, stores, tables);
+        }
+    }
+}
+
+fn insert_candle(candle: &Candle, close: f64, is_closed: bool, slot: u64, stores: &CandleStores, tables: &mut Tables) {
+    let key = candle.key();
+    tables.create_row(&format!("candles_{}", candle.interval), [("market_type", candle.market_type.to_string()), ("market", candle.market.clone()), ("quote_mint", candle.quote_mint.clone()), ("timestamp", candle.timestamp.to_string())])
+        .set("open", stores.opens.get_last(&key).unwrap().to_string())
+        .set("high", stores.highs.get_last(&key).unwrap().to_string())
+        .set("low", stores.lows.get_last(&key).unwrap().to_string())
+        .set("close", close.to_string())
+        .set("base_volume", stores.volumes.get_last(format!("{key}:base")).unwrap().to_string())
+        .set("quote_volume", stores.volumes.get_last(format!("{key}:quote")).unwrap().to_string())
+        .set("is_closed", is_closed)
+        .set("slot", slot);
+}
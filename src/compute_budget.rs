@@ -0,0 +1,110 @@
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::{Pubkey, PubkeyRef};
+use substreams_solana_utils::system_program::constants::SYSTEM_PROGRAM_ID;
+
+use crate::address_lookup_tables::ADDRESS_LOOKUP_TABLE_PROGRAM_ID;
+use crate::bpf_loader_upgradeable::BPF_LOADER_UPGRADEABLE_PROGRAM_ID;
+use crate::decoding::Reader;
+use crate::instruction::IndexedInstruction;
+use crate::stake_program::STAKE_PROGRAM_ID;
+use crate::votes::VOTE_PROGRAM_ID;
+
+/// ComputeBudget111111111111111111111111111111
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = Pubkey([3, 6, 70, 111, 229, 33, 23, 50, 255, 236, 173, 186, 114, 195, 155, 231, 188, 140, 229, 187, 197, 247, 18, 107, 44, 67, 155, 58, 64, 0, 0, 0]);
+
+const REQUEST_HEAP_FRAME_TAG: u8 = 1;
+const SET_COMPUTE_UNIT_LIMIT_TAG: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE_TAG: u8 = 3;
+const SET_LOADED_ACCOUNTS_DATA_SIZE_LIMIT_TAG: u8 = 4;
+
+/// Compute units given to each instruction of a transaction which doesn't set its limit.
+const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+/// Compute units given instead to each instruction of a builtin program.
+const DEFAULT_BUILTIN_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 3_000;
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Programs built into the runtime, including the Compute Budget program itself.
+const BUILTIN_PROGRAM_IDS: [Pubkey; 12] = [
+    SYSTEM_PROGRAM_ID,
+    VOTE_PROGRAM_ID,
+    STAKE_PROGRAM_ID,
+    COMPUTE_BUDGET_PROGRAM_ID,
+    ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+    // Config1111111111111111111111111111111111111
+    Pubkey([3, 6, 74, 163, 0, 47, 116, 220, 200, 110, 67, 49, 15, 12, 5, 42, 248, 197, 218, 39, 246, 16, 64, 25, 163, 35, 239, 160, 0, 0, 0, 0]),
+    // BPFLoader1111111111111111111111111111111111
+    Pubkey([2, 168, 246, 145, 78, 136, 161, 107, 189, 35, 149, 133, 95, 100, 4, 217, 180, 244, 86, 183, 130, 27, 176, 20, 87, 73, 66, 140, 0, 0, 0, 0]),
+    // BPFLoader2111111111111111111111111111111111
+    Pubkey([2, 168, 246, 145, 78, 136, 161, 110, 57, 90, 225, 40, 148, 143, 250, 105, 86, 147, 55, 104, 24, 221, 71, 67, 82, 33, 243, 198, 0, 0, 0, 0]),
+    // LoaderV411111111111111111111111111111111111
+    Pubkey([5, 18, 180, 17, 81, 81, 227, 122, 173, 10, 139, 197, 211, 136, 46, 123, 127, 218, 76, 243, 210, 192, 40, 200, 207, 131, 54, 24, 0, 0, 0, 0]),
+    // Ed25519SigVerify111111111111111111111111111
+    Pubkey([3, 125, 70, 214, 124, 147, 251, 190, 18, 249, 66, 143, 131, 141, 64, 255, 5, 112, 116, 73, 39, 244, 138, 100, 252, 202, 112, 68, 128, 0, 0, 0]),
+    // KeccakSecp256k11111111111111111111111111111
+    Pubkey([4, 198, 252, 32, 240, 80, 204, 240, 85, 132, 215, 33, 28, 159, 140, 245, 158, 193, 71, 133, 187, 22, 106, 30, 40, 48, 232, 18, 32, 0, 0, 0]),
+];
+
+/// Compute unit limit of a transaction which doesn't set it, from the programs of its top level instructions.
+pub fn get_default_compute_unit_limit<'a>(program_ids: impl Iterator<Item = PubkeyRef<'a>>) -> u32 {
+    program_ids
+        .map(|program_id| if BUILTIN_PROGRAM_IDS.iter().any(|builtin| program_id == *builtin) {
+            DEFAULT_BUILTIN_INSTRUCTION_COMPUTE_UNIT_LIMIT
+        } else {
+            DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT
+        })
+        .fold(0u32, |limit, x| limit.saturating_add(x))
+}
+
+#[derive(Debug)]
+pub enum ComputeBudgetEvent {
+    RequestHeapFrame { bytes: u32 },
+    SetComputeUnitLimit { units: u32 },
+    /// Price in micro-lamports per compute unit.
+    SetComputeUnitPrice { micro_lamports: u64 },
+    SetLoadedAccountsDataSizeLimit { bytes: u32 },
+}
+
+pub fn parse_instruction(instruction: &IndexedInstruction) -> Result<Option<ComputeBudgetEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let tag = match reader.read_u8() {
+        Ok(tag) => tag,
+        Err(_) => return Ok(None),
+    };
+    let event = match tag {
+        REQUEST_HEAP_FRAME_TAG => ComputeBudgetEvent::RequestHeapFrame { bytes: reader.read_u32()? },
+        SET_COMPUTE_UNIT_LIMIT_TAG => ComputeBudgetEvent::SetComputeUnitLimit { units: reader.read_u32()? },
+        SET_COMPUTE_UNIT_PRICE_TAG => ComputeBudgetEvent::SetComputeUnitPrice { micro_lamports: reader.read_u64()? },
+        SET_LOADED_ACCOUNTS_DATA_SIZE_LIMIT_TAG => ComputeBudgetEvent::SetLoadedAccountsDataSizeLimit { bytes: reader.read_u32()? },
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+/// The compute budget requested by a transaction, from its Compute Budget instructions.
+#[derive(Debug, Default)]
+pub struct ComputeBudget {
+    pub compute_unit_limit: Option<u32>,
+    pub compute_unit_price: Option<u64>,
+}
+
+impl ComputeBudget {
+    pub fn update(&mut self, event: &ComputeBudgetEvent) {
+        match event {
+            ComputeBudgetEvent::SetComputeUnitLimit { units } => self.compute_unit_limit = Some(*units),
+            ComputeBudgetEvent::SetComputeUnitPrice { micro_lamports } => self.compute_unit_price = Some(*micro_lamports),
+            _ => (),
+        }
+    }
+
+    /// Compute unit limit of the transaction, which is `default_limit` without an explicit limit.
+    pub fn get_compute_unit_limit(&self, default_limit: u32) -> u32 {
+        self.compute_unit_limit.unwrap_or(default_limit).min(MAX_COMPUTE_UNIT_LIMIT)
+    }
+
+    /// Priority fee in micro-lamports, which is the price of all the compute units requested.
+    pub fn get_priority_fee(&self, default_limit: u32) -> u64 {
+        self.compute_unit_price.unwrap_or(0).saturating_mul(self.get_compute_unit_limit(default_limit) as u64)
+    }
+}
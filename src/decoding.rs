@@ -0,0 +1,156 @@
+use anyhow::{anyhow, Error};
+
+use substreams::log;
+
+use substreams_solana_utils::log::Log;
+use substreams_solana_utils::pubkey::Pubkey;
+
+use crate::instruction::IndexedInstruction;
+
+/// Tag of the self-CPI instructions used by Anchor's `emit_cpi!` to log events.
+pub const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
+/// Sequential little-endian reader over instruction or event data.
+pub struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+#[allow(unused)]
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Reader { data, offset: 0 }
+    }
+
+    pub fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        let bytes = self.data.get(self.offset..self.offset + length).ok_or(anyhow!("Unexpected end of data at offset {}.", self.offset))?;
+        self.offset += length;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, Error> { Ok(self.read_bytes(1)?[0]) }
+    pub fn read_bool(&mut self) -> Result<bool, Error> { Ok(self.read_u8()? != 0) }
+    pub fn read_u16(&mut self) -> Result<u16, Error> { Ok(u16::from_le_bytes(self.read_bytes(2)?.try_into()?)) }
+    pub fn read_u32(&mut self) -> Result<u32, Error> { Ok(u32::from_le_bytes(self.read_bytes(4)?.try_into()?)) }
+    pub fn read_i32(&mut self) -> Result<i32, Error> { Ok(i32::from_le_bytes(self.read_bytes(4)?.try_into()?)) }
+    pub fn read_u64(&mut self) -> Result<u64, Error> { Ok(u64::from_le_bytes(self.read_bytes(8)?.try_into()?)) }
+    pub fn read_i64(&mut self) -> Result<i64, Error> { Ok(i64::from_le_bytes(self.read_bytes(8)?.try_into()?)) }
+    pub fn read_u128(&mut self) -> Result<u128, Error> { Ok(u128::from_le_bytes(self.read_bytes(16)?.try_into()?)) }
+    pub fn read_pubkey(&mut self) -> Result<String, Error> { Ok(bs58::encode(self.read_bytes(32)?).into_string()) }
+
+    /// Reads a Borsh `Option`, i.e. a presence byte followed by the value.
+    pub fn read_option<T>(&mut self, read: impl FnOnce(&mut Self) -> Result<T, Error>) -> Result<Option<T>, Error> {
+        match self.read_bool()? {
+            true => Ok(Some(read(self)?)),
+            false => Ok(None),
+        }
+    }
+
+    /// Reads an unsigned integer encoded in groups of 7 bits, least significant first, as used by compact arrays.
+    pub fn read_varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(anyhow!("Invalid varint at offset {}.", self.offset))
+    }
+
+    /// Whether all the data was read.
+    pub fn is_empty(&self) -> bool {
+        self.offset >= self.data.len()
+    }
+
+    /// Reads an Anchor discriminator.
+    pub fn read_discriminator(&mut self) -> Result<[u8; 8], Error> {
+        Ok(self.read_bytes(8)?.try_into()?)
+    }
+}
+
+/// Returns the data of the Anchor events emitted through `emit_cpi!` by `instruction`, discriminator included.
+pub fn get_cpi_events(instruction: &IndexedInstruction, program_id: Pubkey) -> Vec<Vec<u8>> {
+    instruction.inner_instructions().iter()
+        .filter(|inner_instruction| inner_instruction.program_id() == program_id && inner_instruction.data().starts_with(&EVENT_IX_TAG))
+        .map(|inner_instruction| inner_instruction.data()[EVENT_IX_TAG.len()..].to_vec())
+        .collect()
+}
+
+/// Returns the data of the Anchor events emitted through `emit!` by `instruction`, discriminator included.
+pub fn get_log_events(instruction: &IndexedInstruction) -> Vec<Vec<u8>> {
+    instruction.logs().iter().flatten()
+        .filter_map(|log| match log {
+            Log::Data(data_log) => data_log.data().ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the data following `discriminator` of every event which has it, in emission order.
+pub fn find_events(events: &[Vec<u8>], discriminator: [u8; 8]) -> Vec<&[u8]> {
+    events.iter().filter(|event| event.starts_with(&discriminator)).map(|event| &event[discriminator.len()..]).collect()
+}
+
+/// Returns the data following `discriminator` of the first event which has it.
+pub fn find_event(events: &[Vec<u8>], discriminator: [u8; 8]) -> Option<&[u8]> {
+    find_events(events, discriminator).first().copied()
+}
+
+/// Decodes the payload of an event of `instruction` with `decode`, or logs why it couldn't and returns `None`.
+///
+/// Event layouts change with program upgrades, so a payload which doesn't decode mustn't fail the whole block.
+pub fn decode_event<T>(instruction: &IndexedInstruction, data: &[u8], decode: impl FnOnce(&mut Reader) -> Result<T, Error>) -> Option<T> {
+    match decode(&mut Reader::new(data)) {
+        Ok(event) => Some(event),
+        Err(error) => {
+            log::info!("Skipping an event of instruction {} which couldn't be decoded: {}", instruction.index, error);
+            None
+        },
+    }
+}
+
+/// Returns the address of the account at `index` in the accounts of `instruction`.
+pub fn get_account(instruction: &IndexedInstruction, index: usize) -> Result<String, Error> {
+    instruction.accounts().get(index).map(|x| x.to_string()).ok_or(anyhow!("Missing account {} of instruction {}.", index, instruction.index))
+}
+
+/// An element of an `Array` column, encoded as a JSON value.
+pub trait ArrayElement {
+    fn to_json(&self) -> String;
+}
+
+impl ArrayElement for String {
+    fn to_json(&self) -> String {
+        let mut json = String::with_capacity(self.len() + 2);
+        json.push('"');
+        for c in self.chars() {
+            match c {
+                '"' => json.push_str("\\\""),
+                '\\' => json.push_str("\\\\"),
+                c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+                c => json.push(c),
+            }
+        }
+        json.push('"');
+        json
+    }
+}
+
+impl ArrayElement for u8 {
+    fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ArrayElement for u64 {
+    fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Formats `values` as a JSON array, which the ClickHouse dialect of the SQL sink decodes into `Array` columns.
+pub fn to_array_value<T: ArrayElement>(values: &[T]) -> String {
+    format!("[{}]", values.iter().map(|x| x.to_json()).collect::<Vec<_>>().join(","))
+}
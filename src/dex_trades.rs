@@ -0,0 +1,219 @@
+use anyhow::{anyhow, Error};
+
+use substreams::store::StoreGetString;
+use substreams_database_change::tables::Tables;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use substreams_solana_utils::transaction::{get_context, get_signers, TransactionContext};
+
+use raydium_amm_substream::raydium_amm::constants::RAYDIUM_AMM_PROGRAM_ID;
+use raydium_amm_substream::pb::raydium_amm::raydium_amm_event;
+use pumpfun_substream::pumpfun::PUMPFUN_PROGRAM_ID;
+use pumpfun_substream::pb::pumpfun::pumpfun_event;
+
+use crate::balances::PostTokenBalances;
+use crate::instruction::{get_indexed_instructions, IndexedInstruction, IndexedInstructions};
+use crate::raydium_clmm::{self, RaydiumClmmEvent, RAYDIUM_CLMM_PROGRAM_ID};
+use crate::raydium_cpmm::{self, RaydiumCpmmEvent, RAYDIUM_CPMM_PROGRAM_ID};
+use crate::whirlpool::{self, WhirlpoolEvent, WHIRLPOOL_PROGRAM_ID};
+use crate::meteora_dlmm::{self, MeteoraDlmmEvent, METEORA_DLMM_PROGRAM_ID};
+use crate::pumpswap::{self, PumpswapEvent, PUMPSWAP_PROGRAM_ID};
+use crate::mints::MintDecimals;
+use crate::pb::dex_trades::{DexTrade, DexTrades};
+use crate::prices::UsdPrices;
+
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+pub const USDT_MINT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
+
+/// Mints used as the quote side of a trade, by order of preference.
+pub const QUOTE_MINTS: [&str; 3] = [USDC_MINT, USDT_MINT, WSOL_MINT];
+
+/// Normalizes the swaps of every supported DEX into a single `DexTrade` shape.
+#[substreams::handlers::map]
+fn map_dex_trades(block: Block, mints_store: StoreGetString) -> Result<DexTrades, Error> {
+    let mut trades: Vec<DexTrade> = Vec::new();
+    for (index, transaction) in block.transactions.iter().enumerate() {
+        if transaction.meta.as_ref().unwrap().err.is_some() {
+            continue;
+        }
+        let instructions = get_indexed_instructions(transaction)?;
+        let mut context = get_context(transaction)?;
+        let mint_decimals = MintDecimals::new(transaction, &mints_store);
+        let post_token_balances = PostTokenBalances::new(transaction);
+        let signer = get_signers(transaction).first().cloned().unwrap_or_default();
+
+        for instruction in instructions.flattened().iter() {
+            context.update_balance(&instruction.instruction.instruction);
+            for (trade_index, mut trade) in parse_dex_trades(instruction, &context, &post_token_balances)?.into_iter().enumerate() {
+                trade.transaction_index = index as u32;
+                trade.instruction_index = instruction.index;
+                trade.trade_index = trade_index as u32;
+                trade.signature = context.signature.clone();
+                trade.signer = signer.clone();
+                trade.base_decimals = mint_decimals.get(&trade.base_mint);
+                trade.quote_decimals = mint_decimals.get(&trade.quote_mint);
+                trade.price = get_price(&trade);
+                if let Some(parent_instruction) = instruction.parent_instruction() {
+                    let top_instruction = instruction.top_instruction().unwrap();
+                    trade.parent_instruction_index = parent_instruction.index;
+                    trade.parent_instruction_program_id = parent_instruction.program_id().to_string();
+                    trade.top_instruction_index = top_instruction.index;
+                    trade.top_instruction_program_id = top_instruction.program_id().to_string();
+                } else {
+                    trade.parent_instruction_index = -1;
+                    trade.top_instruction_index = -1;
+                }
+                trades.push(trade);
+            }
+        }
+    }
+    Ok(DexTrades { trades })
+}
+
+/// Returns the trades made by `instruction`, with only the protocol specific fields set.
+///
+/// Multi-hop instructions make one trade per pool, which share the instruction index and are told apart by
+/// their trade index.
+fn parse_dex_trades(instruction: &IndexedInstruction, context: &TransactionContext, post_token_balances: &PostTokenBalances) -> Result<Vec<DexTrade>, Error> {
+    let program_id = instruction.program_id();
+    let trade = if program_id == RAYDIUM_AMM_PROGRAM_ID {
+        match raydium_amm_substream::parse_instruction(&instruction.instruction, context).map_err(|x| anyhow!(x))? {
+            Some(raydium_amm_event::Event::Swap(swap)) => {
+                Some(new_dex_trade("raydium_amm", &swap.amm, &swap.user, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out))
+            },
+            _ => None,
+        }
+    } else if program_id == RAYDIUM_CLMM_PROGRAM_ID {
+        match raydium_clmm::parse_instruction(instruction, context)? {
+            Some(RaydiumClmmEvent::Swap(swap)) => {
+                Some(new_dex_trade("raydium_clmm", &swap.pool_state, &swap.user, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out))
+            },
+            _ => None,
+        }
+    } else if program_id == RAYDIUM_CPMM_PROGRAM_ID {
+        match raydium_cpmm::parse_instruction(instruction, context, post_token_balances)? {
+            Some(RaydiumCpmmEvent::Swap(swap)) => {
+                Some(new_dex_trade("raydium_cpmm", &swap.pool_state, &swap.user, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out))
+            },
+            _ => None,
+        }
+    } else if program_id == WHIRLPOOL_PROGRAM_ID {
+        match whirlpool::parse_instruction(instruction, context)? {
+            Some(WhirlpoolEvent::Swap(swap)) => {
+                Some(new_dex_trade("whirlpool", &swap.whirlpool, &swap.user, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out))
+            },
+            Some(WhirlpoolEvent::TwoHopSwap(swap_one, swap_two)) => {
+                return Ok([swap_one, swap_two].iter()
+                    .map(|swap| new_dex_trade("whirlpool", &swap.whirlpool, &swap.user, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out))
+                    .collect());
+            },
+            _ => None,
+        }
+    } else if program_id == METEORA_DLMM_PROGRAM_ID {
+        match meteora_dlmm::parse_instruction(instruction)? {
+            Some(MeteoraDlmmEvent::Swap(swap)) => {
+                Some(new_dex_trade("meteora_dlmm", &swap.lb_pair, &swap.user, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out))
+            },
+            _ => None,
+        }
+    } else if program_id == PUMPFUN_PROGRAM_ID {
+        match pumpfun_substream::parse_instruction(&instruction.instruction, context)? {
+            Some(pumpfun_event::Event::Swap(swap)) => {
+                let sol_amount = swap.sol_amount.unwrap_or(0);
+                match swap.direction.as_str() {
+                    "buy" => Some(new_dex_trade("pumpfun", &swap.bonding_curve, &swap.user, WSOL_MINT, sol_amount, &swap.mint, swap.token_amount)),
+                    _ => Some(new_dex_trade("pumpfun", &swap.bonding_curve, &swap.user, &swap.mint, swap.token_amount, WSOL_MINT, sol_amount)),
+                }
+            },
+            _ => None,
+        }
+    } else if program_id == PUMPSWAP_PROGRAM_ID {
+        match pumpswap::parse_instruction(instruction)? {
+            Some(PumpswapEvent::Swap(swap)) => match swap.direction {
+                "buy" => Some(new_dex_trade("pumpswap", &swap.pool, &swap.user, &swap.quote_mint, swap.quote_amount, &swap.base_mint, swap.base_amount)),
+                _ => Some(new_dex_trade("pumpswap", &swap.pool, &swap.user, &swap.base_mint, swap.base_amount, &swap.quote_mint, swap.quote_amount)),
+            },
+            _ => None,
+        }
+    } else {
+        None
+    };
+    Ok(trade.into_iter().collect())
+}
+
+/// Builds a trade from what the trader sent to the pool (`mint_in`) and received from it (`mint_out`).
+///
+/// The quote mint is the preferred mint of `QUOTE_MINTS` involved in the swap, or `mint_out` if there's none.
+pub fn new_dex_trade(
+    protocol: &str,
+    pool: &str,
+    trader: &str,
+    mint_in: &str,
+    amount_in: u64,
+    mint_out: &str,
+    amount_out: u64,
+) -> DexTrade {
+    let quote_rank = |mint: &str| QUOTE_MINTS.iter().position(|x| *x == mint).unwrap_or(QUOTE_MINTS.len());
+    let is_buy = quote_rank(mint_in) < quote_rank(mint_out);
+    let (base_mint, base_amount, quote_mint, quote_amount) = match is_buy {
+        true => (mint_out, amount_out, mint_in, amount_in),
+        false => (mint_in, amount_in, mint_out, amount_out),
+    };
+    DexTrade {
+        protocol: protocol.to_string(),
+        pool: pool.to_string(),
+        trader: trader.to_string(),
+        base_mint: base_mint.to_string(),
+        base_amount,
+        quote_mint: quote_mint.to_string(),
+        quote_amount,
+        side: if is_buy { "buy" } else { "sell" }.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Price of the base mint in units of the quote mint, adjusted by the mint decimals.
+fn get_price(trade: &DexTrade) -> Option<f64> {
+    if trade.base_amount == 0 {
+        return None;
+    }
+    Some(get_quote_ui_amount(trade)? / get_base_ui_amount(trade)?)
+}
+
+pub fn get_base_ui_amount(trade: &DexTrade) -> Option<f64> {
+    trade.base_decimals.map(|decimals| trade.base_amount as f64 / 10f64.powi(decimals as i32))
+}
+
+pub fn get_quote_ui_amount(trade: &DexTrade) -> Option<f64> {
+    trade.quote_decimals.map(|decimals| trade.quote_amount as f64 / 10f64.powi(decimals as i32))
+}
+
+pub fn insert_dex_trades(dex_trades: &DexTrades, slot: u64, blockhash: &String, usd_prices: &UsdPrices, tables: &mut Tables) {
+    for trade in dex_trades.trades.iter() {
+        let row = tables.create_row("dex_trades", [("slot", slot.to_string()), ("transaction_index", trade.transaction_index.to_string()), ("instruction_index", trade.instruction_index.to_string()), ("trade_index", trade.trade_index.to_string())])
+            .set("partial_signature", &trade.signature[0..4])
+            .set("partial_blockhash", &blockhash[0..4])
+            .set("protocol", &trade.protocol)
+            .set("pool", &trade.pool)
+            .set("trader", &trade.trader)
+            .set("signer", &trade.signer)
+            .set("base_mint", &trade.base_mint)
+            .set("quote_mint", &trade.quote_mint)
+            .set("base_amount", trade.base_amount)
+            .set("quote_amount", trade.quote_amount)
+            .set("side", &trade.side)
+            .set("parent_instruction_index", trade.parent_instruction_index)
+            .set("top_instruction_index", trade.top_instruction_index)
+            .set("parent_instruction_program_id", &trade.parent_instruction_program_id)
+            .set("top_instruction_program_id", &trade.top_instruction_program_id);
+        if let Some(price) = trade.price {
+            row.set("price", price.to_string());
+        }
+        let usd_value = usd_prices.get_usd_value(&trade.quote_mint, trade.quote_amount, trade.quote_decimals)
+            .or_else(|| usd_prices.get_usd_value(&trade.base_mint, trade.base_amount, trade.base_decimals));
+        if let Some(usd_value) = usd_value {
+            row.set("usd_value", usd_value.to_string());
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::{anyhow, Error};
+
+use substreams_solana_utils::pubkey::Pubkey;
+
+use crate::decoding::{decode_event, find_event, find_events, get_account, get_cpi_events, get_log_events, Reader};
+use crate::instruction::IndexedInstruction;
+
+/// JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4
+pub const JUPITER_V6_PROGRAM_ID: Pubkey = Pubkey([4, 121, 213, 91, 242, 49, 192, 110, 238, 116, 197, 110, 206, 104, 21, 7, 253, 177, 178, 222, 163, 244, 142, 81, 2, 177, 205, 162, 86, 188, 19, 143]);
+
+const ROUTE_DISCRIMINATOR: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+const SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+const EXACT_OUT_ROUTE_DISCRIMINATOR: [u8; 8] = [208, 51, 239, 151, 123, 43, 237, 92];
+const SHARED_ACCOUNTS_EXACT_OUT_ROUTE_DISCRIMINATOR: [u8; 8] = [176, 209, 105, 168, 154, 125, 69, 62];
+
+const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [64, 198, 205, 232, 38, 8, 113, 226];
+const FEE_EVENT_DISCRIMINATOR: [u8; 8] = [73, 79, 78, 127, 184, 213, 13, 220];
+
+/// Size of the amounts, the slippage and the platform fee which end the arguments of every route instruction.
+const ROUTE_ARGS_TAIL_SIZE: usize = 8 + 8 + 2 + 1;
+
+/// The trade made through a whole Jupiter route, from the swap events of its steps.
+///
+/// The quoted amount is the quoted output for exact input routes, and the quoted input for exact output routes.
+/// The hops are the steps of the route plan, the legs of split routes included.
+#[derive(Debug)]
+pub struct RouteEvent {
+    pub route_type: &'static str,
+    pub trader: String,
+    pub input_mint: String,
+    pub input_amount: u64,
+    pub output_mint: String,
+    pub output_amount: u64,
+    pub quoted_amount: u64,
+    pub slippage_bps: u16,
+    pub platform_fee_bps: u8,
+    pub platform_fee_amount: Option<u64>,
+    pub platform_fee_mint: Option<String>,
+    pub hops: u32,
+}
+
+struct SwapEvent {
+    input_mint: String,
+    input_amount: u64,
+    output_mint: String,
+    output_amount: u64,
+}
+
+pub fn parse_instruction(instruction: &IndexedInstruction) -> Result<Option<RouteEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let discriminator = match reader.read_discriminator() {
+        Ok(discriminator) => discriminator,
+        Err(_) => return Ok(None),
+    };
+    let (route_type, trader_index) = match discriminator {
+        ROUTE_DISCRIMINATOR => ("route", 1),
+        SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR => ("shared_accounts_route", 2),
+        EXACT_OUT_ROUTE_DISCRIMINATOR => ("exact_out_route", 1),
+        SHARED_ACCOUNTS_EXACT_OUT_ROUTE_DISCRIMINATOR => ("shared_accounts_exact_out_route", 2),
+        _ => return Ok(None),
+    };
+    if discriminator == SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR || discriminator == SHARED_ACCOUNTS_EXACT_OUT_ROUTE_DISCRIMINATOR {
+        let _program_authority_id = reader.read_u8()?;
+    }
+    let hops = reader.read_u32()?;
+
+    // The route plan steps have a variable size, so the fixed arguments are read from the end.
+    let data = instruction.data();
+    let tail = data.len().checked_sub(ROUTE_ARGS_TAIL_SIZE).ok_or(anyhow!("Route instruction {} is too short.", instruction.index))?;
+    let mut reader = Reader::new(&data[tail..]);
+    let _amount = reader.read_u64()?;
+    let quoted_amount = reader.read_u64()?;
+    let slippage_bps = reader.read_u16()?;
+    let platform_fee_bps = reader.read_u8()?;
+
+    // Older versions of the program log their events instead of emitting them through self-CPIs.
+    let mut events = get_cpi_events(instruction, JUPITER_V6_PROGRAM_ID);
+    if events.is_empty() {
+        events = get_log_events(instruction);
+    }
+    // The amounts of a route can't be summed if any of its swaps is missing.
+    let swaps = find_events(&events, SWAP_EVENT_DISCRIMINATOR).into_iter()
+        .map(|data| decode_event(instruction, data, read_swap_event))
+        .collect::<Option<Vec<_>>>();
+    let swaps = match swaps {
+        Some(swaps) => swaps,
+        None => return Ok(None),
+    };
+    let (input_mint, output_mint) = match (swaps.first(), swaps.last()) {
+        (Some(first), Some(last)) => (first.input_mint.clone(), last.output_mint.clone()),
+        _ => return Ok(None),
+    };
+    // Split routes swap the same mints in several steps.
+    let input_amount = swaps.iter().filter(|x| x.input_mint == input_mint).map(|x| x.input_amount).sum();
+    let output_amount = swaps.iter().filter(|x| x.output_mint == output_mint).map(|x| x.output_amount).sum();
+
+    let fee = find_event(&events, FEE_EVENT_DISCRIMINATOR).and_then(|data| decode_event(instruction, data, |event| {
+        // Skips the fee account.
+        event.read_bytes(32)?;
+        Ok((event.read_pubkey()?, event.read_u64()?))
+    }));
+    let (platform_fee_mint, platform_fee_amount) = match fee {
+        Some((mint, amount)) => (Some(mint), Some(amount)),
+        None => (None, None),
+    };
+
+    Ok(Some(RouteEvent {
+        route_type,
+        trader: get_account(instruction, trader_index)?,
+        input_mint,
+        input_amount,
+        output_mint,
+        output_amount,
+        quoted_amount,
+        slippage_bps,
+        platform_fee_bps,
+        platform_fee_amount,
+        platform_fee_mint,
+        hops,
+    }))
+}
+
+/// Reads the `SwapEvent` of a step of a route.
+fn read_swap_event(event: &mut Reader) -> Result<SwapEvent, Error> {
+    // Skips the AMM.
+    event.read_bytes(32)?;
+    Ok(SwapEvent {
+        input_mint: event.read_pubkey()?,
+        input_amount: event.read_u64()?,
+        output_mint: event.read_pubkey()?,
+        output_amount: event.read_u64()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A step of a route swapping 1 SOL for USDC, encoded by hand after the layout of the program's IDL.
+    fn swap_event_data() -> Vec<u8> {
+        let mut data = [1u8; 32].to_vec();
+        data.extend(bs58::decode("So11111111111111111111111111111111111111112").into_vec().unwrap());
+        data.extend(1_000_000_000u64.to_le_bytes());
+        data.extend(bs58::decode("EPjFWdd5AufqSSqeM2qyyEPvUBNTZdJmSdKjhdMnX1Ph").into_vec().unwrap());
+        data.extend(142_977_620u64.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_read_swap_event() {
+        let data = swap_event_data();
+        let mut reader = Reader::new(&data);
+        let event = read_swap_event(&mut reader).unwrap();
+        assert!(reader.is_empty());
+        assert_eq!((event.input_mint.as_str(), event.input_amount), ("So11111111111111111111111111111111111111112", 1_000_000_000));
+        assert_eq!((event.output_mint.as_str(), event.output_amount), ("EPjFWdd5AufqSSqeM2qyyEPvUBNTZdJmSdKjhdMnX1Ph", 142_977_620));
+    }
+
+    #[test]
+    fn test_read_truncated_swap_event() {
+        let data = swap_event_data();
+        assert!(read_swap_event(&mut Reader::new(&data[..data.len() - 1])).is_err());
+    }
+}
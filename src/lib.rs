@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Error, Context};
 
+use substreams::store::{DeltaFloat64, DeltaString, Deltas, StoreGetBigInt, StoreGetFloat64, StoreGetString};
 use substreams_database_change::pb::database::DatabaseChanges;
 use substreams_database_change::tables::{Row, Tables};
 use substreams_solana::pb::sf::solana::r#type::v1::{Block, ConfirmedTransaction};
@@ -26,53 +27,220 @@ use pumpfun_substream::pb::pumpfun::pumpfun_event;
 use system_program_substream;
 use system_program_substream::pb::system_program::system_program_event;
 
+mod pb;
+use pb::block_events::BlockEvents;
+use pb::dex_trades::DexTrades;
+
 mod instruction;
 use instruction::{get_indexed_instructions, IndexedInstruction, IndexedInstructions};
 
+mod block_events;
+
+mod balances;
+use balances::{insert_token_holder_balances, PostTokenBalances};
+
+mod mints;
+use mints::{get_ui_amount, insert_mint, MintDecimals, MintStores, SOL_DECIMALS};
+
+mod dex_trades;
+use dex_trades::insert_dex_trades;
+
+mod candles;
+use candles::{insert_candles, CandleStores};
+
+mod prices;
+use prices::UsdPrices;
+
+mod raydium_pools;
+use raydium_pools::{insert_raydium_pool, insert_raydium_pool_state, RaydiumPools};
+
+mod raydium_amm;
+use raydium_amm::{get_market, get_open_time, insert_raydium_amm_other_event, set_swap_limit_columns};
+
+mod decoding;
+use decoding::to_array_value;
+
+mod token_transfers;
+
+mod raydium_clmm;
+use raydium_clmm::{RaydiumClmmEvent, RAYDIUM_CLMM_PROGRAM_ID};
+
+mod raydium_cpmm;
+use raydium_cpmm::{RaydiumCpmmEvent, RAYDIUM_CPMM_PROGRAM_ID};
+
+mod whirlpool;
+use whirlpool::{WhirlpoolEvent, WHIRLPOOL_PROGRAM_ID};
+
+mod meteora_dlmm;
+use meteora_dlmm::{MeteoraDlmmEvent, METEORA_DLMM_PROGRAM_ID};
+
+mod jupiter;
+use jupiter::JUPITER_V6_PROGRAM_ID;
+
+mod pumpfun_tokens;
+use pumpfun_tokens::{insert_pumpfun_token, PumpfunTokens};
+
+mod pumpfun_fees;
+use pumpfun_fees::{get_fee_lamports, get_transferred_fee, insert_pumpfun_revenue, PumpfunFees};
+
+mod pumpswap;
+use pumpswap::{PumpswapEvent, PUMPSWAP_PROGRAM_ID};
+
+mod openbook_v2;
+use openbook_v2::OPENBOOK_V2_PROGRAM_ID;
+
+mod phoenix;
+use phoenix::PHOENIX_PROGRAM_ID;
+
+mod order_books;
+use order_books::{OrderBookEvent, OrderBookMarkets};
+
+mod stake_program;
+use stake_program::{StakeProgramEvent, STAKE_PROGRAM_ID};
+
+mod compute_budget;
+use compute_budget::{get_default_compute_unit_limit, ComputeBudget, ComputeBudgetEvent, COMPUTE_BUDGET_PROGRAM_ID};
+
+mod address_lookup_tables;
+use address_lookup_tables::{get_transaction_lookup_tables, get_version, ADDRESS_LOOKUP_TABLE_PROGRAM_ID};
+
+mod associated_token_accounts;
+use associated_token_accounts::ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID;
+
+mod bpf_loader_upgradeable;
+use bpf_loader_upgradeable::{BufferWrites, BPF_LOADER_UPGRADEABLE_PROGRAM_ID};
+
+mod memo;
+use memo::{MEMO_V1_PROGRAM_ID, MEMO_V2_PROGRAM_ID};
+
+mod votes;
+use votes::{insert_vote_counts, is_vote_transaction, parse_vote_transaction, VoteCounts, VoteMode};
+
 #[substreams::handlers::map]
-fn block_database_changes(block: Block) -> Result<DatabaseChanges, Error> {
+// The handler takes one argument per module input, which are bundled into `Stores` right away.
+#[allow(clippy::too_many_arguments)]
+fn block_database_changes(
+    params: String,
+    block: Block,
+    token_account_balance_deltas: Deltas<DeltaString>,
+    mints_store: StoreGetString,
+    mint_supplies_store: StoreGetBigInt,
+    block_events: BlockEvents,
+    dex_trades: DexTrades,
+    candle_opens_store: StoreGetFloat64,
+    candle_highs_store: StoreGetFloat64,
+    candle_lows_store: StoreGetFloat64,
+    candle_close_deltas: Deltas<DeltaFloat64>,
+    candle_volumes_store: StoreGetFloat64,
+    sol_usd_price_store: StoreGetFloat64,
+    token_usd_prices_store: StoreGetFloat64,
+    raydium_pools_store: StoreGetString,
+    raydium_pool_reserves_store: StoreGetBigInt,
+    pumpfun_tokens_store: StoreGetString,
+    pumpfun_params_store: StoreGetString,
+    pumpfun_revenue_store: StoreGetBigInt,
+    order_book_markets_store: StoreGetString,
+) -> Result<DatabaseChanges, Error> {
+    let stores = Stores {
+        mints: MintStores { mints: mints_store, supplies: mint_supplies_store },
+        candles: CandleStores {
+            opens: candle_opens_store,
+            highs: candle_highs_store,
+            lows: candle_lows_store,
+            close_deltas: candle_close_deltas,
+            volumes: candle_volumes_store,
+        },
+        usd_prices: UsdPrices { sol_usd: sol_usd_price_store, token_usd: token_usd_prices_store },
+        raydium_pools: RaydiumPools { store: raydium_pools_store, reserves: raydium_pool_reserves_store },
+        pumpfun_tokens: PumpfunTokens { store: pumpfun_tokens_store },
+        pumpfun_fees: PumpfunFees::new(pumpfun_params_store, pumpfun_revenue_store, &block_events),
+        order_book_markets: OrderBookMarkets { store: order_book_markets_store },
+    };
+    let vote_mode = VoteMode::from_params(&params)?;
+    let mut vote_counts = VoteCounts::default();
     let mut tables = Tables::new();
     for (index, transaction) in block.transactions.iter().enumerate() {
-        match parse_transaction(transaction, index as u32, block.slot, &block.blockhash, &mut tables)? {
-            true => {
-                let signers = get_signers(transaction);
-                let row = tables.create_row("transactions", [("slot", block.slot.to_string()), ("transaction_index", index.to_string())])
-                    .set("signature", get_signature(transaction))
-                    .set("number_of_signers", signers.len().to_string());
-                for i in 0..8 {
-                    row.set(&format!("signer{i}"), signers.get(i).unwrap_or(&"".into()));
-                }
-            },
-            false => (),
+        if let Some(_) = transaction.meta.as_ref().unwrap().err {
+            continue;
+        }
+        // Vote transactions are most of a block, so they are handled before building any context.
+        if is_vote_transaction(transaction) {
+            parse_vote_transaction(transaction, index as u32, block.slot, &block.blockhash, vote_mode, &mut vote_counts, &mut tables)?;
+        } else {
+            parse_transaction(transaction, index as u32, block.slot, &block.blockhash, &stores, &mut tables)?;
         }
     }
+    insert_token_holder_balances(&token_account_balance_deltas, block.slot, &mut tables);
+    insert_dex_trades(&dex_trades, block.slot, &block.blockhash, &stores.usd_prices, &mut tables);
+    insert_candles(&dex_trades, block.block_time.as_ref().unwrap().timestamp, block.slot, &stores.candles, &mut tables);
+    insert_pumpfun_revenue(&dex_trades, block.block_time.as_ref().unwrap().timestamp, block.slot, &stores.pumpfun_fees, &mut tables);
+    insert_vote_counts(&vote_counts, block.slot, &mut tables);
     tables.create_row("blocks", block.slot.to_string())
         .set("parent_slot", block.parent_slot)
         .set("block_height", block.block_height.as_ref().unwrap().block_height)
         .set("blockhash", block.blockhash)
         .set("previous_blockhash", block.previous_blockhash)
-        .set("block_time", block.block_time.as_ref().unwrap().timestamp);
+        .set("block_time", block.block_time.as_ref().unwrap().timestamp)
+        .set("vote_transactions", vote_counts.transactions);
    Ok(tables.to_database_changes())
 }
 
-fn parse_transaction<'a>(
+/// The stores the database changes are built from.
+struct Stores {
+    mints: MintStores,
+    candles: CandleStores,
+    usd_prices: UsdPrices,
+    raydium_pools: RaydiumPools,
+    pumpfun_tokens: PumpfunTokens,
+    pumpfun_fees: PumpfunFees,
+    order_book_markets: OrderBookMarkets,
+}
+
+/// The decimals and post balances of the token accounts of a transaction, built once for its instructions.
+struct TransactionTokens<'a> {
+    mint_decimals: MintDecimals<'a>,
+    post_token_balances: PostTokenBalances,
+}
+
+fn parse_transaction(
     transaction: &ConfirmedTransaction,
     transaction_index: u32,
     slot: u64,
     blockhash: &String,
+    stores: &Stores,
     tables: &mut Tables,
-) -> Result<bool, Error> {
-    if let Some(_) = transaction.meta.as_ref().unwrap().err {
-        return Ok(false);
-    }
-
+) -> Result<(), Error> {
     let instructions = get_indexed_instructions(transaction)?;
     let mut context = get_context(transaction)?;
+    let tokens = TransactionTokens {
+        mint_decimals: MintDecimals::new(transaction, &stores.mints.mints),
+        post_token_balances: PostTokenBalances::new(transaction),
+    };
 
     let mut tables_changed = false;
+    let mut compute_budget = ComputeBudget::default();
+    let mut buffer_writes = BufferWrites::default();
     for instruction in instructions.flattened().iter() {
         context.update_balance(&instruction.instruction.instruction);
-        match parse_instruction(instruction, &context, tables, slot, transaction_index).with_context(|| format!("Transaction {}", context.signature))? {
+        if instruction.program_id() == COMPUTE_BUDGET_PROGRAM_ID {
+            if let Some(event) = compute_budget::parse_instruction(instruction).with_context(|| format!("Transaction {}", context.signature))? {
+                // The runtime only applies the Compute Budget instructions at the top level.
+                if instruction.parent_instruction().is_none() {
+                    compute_budget.update(&event);
+                }
+                // They only describe the transaction, so they don't get it into the `transactions` table on their own.
+                insert_compute_budget_event(instruction, &event, tables, slot, transaction_index)
+                    .set("partial_signature", &context.signature[0..4])
+                    .set("partial_blockhash", &blockhash[0..4]);
+            }
+            continue;
+        }
+        if instruction.program_id() == BPF_LOADER_UPGRADEABLE_PROGRAM_ID {
+            if let Ok(Some(event)) = bpf_loader_upgradeable::parse_instruction(instruction) {
+                buffer_writes.add(instruction, event);
+            }
+        }
+        match parse_instruction(instruction, &context, stores, &tokens, tables, slot, transaction_index).with_context(|| format!("Transaction {}", context.signature))? {
             Some(row) => {
                 row
                     .set("partial_signature", &context.signature[0..4])
@@ -83,55 +251,128 @@ fn parse_transaction<'a>(
         }
     }
 
-    Ok(tables_changed)
+    for write in buffer_writes.0 {
+        let row = tables.create_row("program_deploy_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", write.instruction.index.to_string())])
+            .set("partial_signature", &context.signature[0..4])
+            .set("partial_blockhash", &blockhash[0..4])
+            .set("type", "write")
+            .set("buffer", write.buffer)
+            .set("authority", write.authority)
+            .set("write_count", write.write_count)
+            .set("data_length", write.data_length)
+            .set("write_offset", write.write_offset);
+        set_parent_instruction_columns(&write.instruction, row);
+        tables_changed = true;
+    }
+
+    if tables_changed {
+        let signers = get_signers(transaction);
+        let default_compute_unit_limit = get_default_compute_unit_limit(instructions.iter().map(|x| x.program_id()));
+        let row = tables.create_row("transactions", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string())])
+            .set("signature", get_signature(transaction))
+            .set("number_of_signers", signers.len().to_string())
+            .set("compute_unit_limit", compute_budget.get_compute_unit_limit(default_compute_unit_limit))
+            .set("compute_unit_price", compute_budget.compute_unit_price.unwrap_or(0))
+            .set("priority_fee_micro_lamports", compute_budget.get_priority_fee(default_compute_unit_limit));
+        for i in 0..8 {
+            row.set(&format!("signer{i}"), signers.get(i).unwrap_or(&"".into()));
+        }
+        let lookup_tables = get_transaction_lookup_tables(transaction);
+        row
+            .set("version", get_version(transaction))
+            .set("lookup_tables", to_array_value(&lookup_tables.iter().map(|x| x.lookup_table.clone()).collect::<Vec<_>>()));
+        for (index, lookup_table) in lookup_tables.into_iter().enumerate() {
+            tables.create_row("transaction_lookup_tables", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("lookup_table_index", index.to_string())])
+                .set("lookup_table", lookup_table.lookup_table)
+                .set("writable_indexes", to_array_value(&lookup_table.writable_indexes))
+                .set("readonly_indexes", to_array_value(&lookup_table.readonly_indexes))
+                .set("writable_addresses", to_array_value(&lookup_table.writable_addresses))
+                .set("readonly_addresses", to_array_value(&lookup_table.readonly_addresses));
+        }
+    }
+
+    Ok(())
 }
 
 fn parse_instruction<'a>(
     instruction: &IndexedInstruction,
     context: &TransactionContext,
+    stores: &Stores,
+    tokens: &TransactionTokens,
     tables: &'a mut Tables,
     slot: u64,
     transaction_index: u32,
 ) -> Result<Option<&'a mut Row>, Error> {
+    let mint_decimals = &tokens.mint_decimals;
+    let usd_prices = &stores.usd_prices;
     let program_id = instruction.program_id();
     let row = if program_id == RAYDIUM_AMM_PROGRAM_ID {
-        parse_raydium_amm_instruction(instruction, context, tables, slot, transaction_index)
+        parse_raydium_amm_instruction(instruction, context, stores, mint_decimals, tables, slot, transaction_index)
+    } else if program_id == RAYDIUM_CLMM_PROGRAM_ID {
+        parse_raydium_clmm_instruction(instruction, context, mint_decimals, usd_prices, tables, slot, transaction_index)
+    } else if program_id == RAYDIUM_CPMM_PROGRAM_ID {
+        parse_raydium_cpmm_instruction(instruction, context, tokens, usd_prices, tables, slot, transaction_index)
+    } else if program_id == WHIRLPOOL_PROGRAM_ID {
+        parse_whirlpool_instruction(instruction, context, mint_decimals, usd_prices, tables, slot, transaction_index)
+    } else if program_id == METEORA_DLMM_PROGRAM_ID {
+        parse_meteora_dlmm_instruction(instruction, mint_decimals, usd_prices, tables, slot, transaction_index)
+    } else if program_id == JUPITER_V6_PROGRAM_ID {
+        parse_jupiter_instruction(instruction, mint_decimals, usd_prices, tables, slot, transaction_index)
+    } else if program_id == OPENBOOK_V2_PROGRAM_ID || program_id == PHOENIX_PROGRAM_ID {
+        parse_order_book_instruction(instruction, context, mint_decimals, &stores.order_book_markets, tables, slot, transaction_index)
     } else if program_id == TOKEN_PROGRAM_ID {
-        parse_spl_token_instruction(instruction, context, tables, slot, transaction_index)
+        parse_spl_token_instruction(instruction, context, stores, mint_decimals, tables, slot, transaction_index)
     } else if program_id == SYSTEM_PROGRAM_ID {
-        parse_system_program_instruction(instruction, context, tables, slot, transaction_index)
+        parse_system_program_instruction(instruction, context, usd_prices, tables, slot, transaction_index)
+    } else if program_id == ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+        parse_address_lookup_table_instruction(instruction, tables, slot, transaction_index)
+    } else if program_id == ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
+        parse_associated_token_account_instruction(instruction, tables, slot, transaction_index)
+    } else if program_id == BPF_LOADER_UPGRADEABLE_PROGRAM_ID {
+        parse_bpf_loader_upgradeable_instruction(instruction, tables, slot, transaction_index)
+    } else if program_id == MEMO_V1_PROGRAM_ID || program_id == MEMO_V2_PROGRAM_ID {
+        parse_memo_instruction(instruction, tables, slot, transaction_index)
+    } else if program_id == STAKE_PROGRAM_ID {
+        parse_stake_program_instruction(instruction, usd_prices, tables, slot, transaction_index)
     } else if program_id == PUMPFUN_PROGRAM_ID {
-        parse_pumpfun_instruction(instruction, context, tables, slot, transaction_index)
+        parse_pumpfun_instruction(instruction, context, stores, mint_decimals, tables, slot, transaction_index)
+    } else if program_id == PUMPSWAP_PROGRAM_ID {
+        parse_pumpswap_instruction(instruction, stores, mint_decimals, tables, slot, transaction_index)
     } else if program_id == MPL_TOKEN_METADATA_PROGRAM_ID {
-        parse_mpl_token_metadata_instruction(instruction, context, tables, slot, transaction_index)
+        parse_mpl_token_metadata_instruction(instruction, context, &stores.mints, tables, slot, transaction_index)
     } else {
         return Ok(None);
     }?;
 
     if let Some(row) = row {
-        if let Some(parent_instruction) = instruction.parent_instruction() {
-            let top_instruction = instruction.top_instruction().unwrap();
-            row
-                .set("parent_instruction_program_id", parent_instruction.program_id().to_string())
-                .set("parent_instruction_index", parent_instruction.index)
-                .set("top_instruction_program_id", top_instruction.program_id().to_string())
-                .set("top_instruction_index", top_instruction.index);
-        } else {
-            row
-                .set("parent_instruction_program_id", "")
-                .set("parent_instruction_index", -1)
-                .set("top_instruction_program_id", "")
-                .set("top_instruction_index", -1);
-        }
+        set_parent_instruction_columns(instruction, row);
         Ok(Some(row))
     } else {
         Ok(None)
     }
 }
 
+fn set_parent_instruction_columns(instruction: &IndexedInstruction, row: &mut Row) {
+    if let Some(parent_instruction) = instruction.parent_instruction() {
+        let top_instruction = instruction.top_instruction().unwrap();
+        row
+            .set("parent_instruction_program_id", parent_instruction.program_id().to_string())
+            .set("parent_instruction_index", parent_instruction.index)
+            .set("top_instruction_program_id", top_instruction.program_id().to_string())
+            .set("top_instruction_index", top_instruction.index);
+    } else {
+        row
+            .set("parent_instruction_program_id", "")
+            .set("parent_instruction_index", -1)
+            .set("top_instruction_program_id", "")
+            .set("top_instruction_index", -1);
+    }
+}
+
 fn parse_system_program_instruction<'a>(
     instruction: &IndexedInstruction,
     context: &TransactionContext,
+    usd_prices: &UsdPrices,
     tables: &'a mut Tables,
     slot: u64,
     transaction_index: u32,
@@ -151,7 +392,7 @@ fn parse_system_program_instruction<'a>(
                 .set("owner", assign.owner)
         },
         Some(system_program_event::Event::Transfer(transfer)) => {
-            tables.create_row("system_program_transfer_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            let row = tables.create_row("system_program_transfer_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("funding_account", transfer.funding_account)
                 .set("funding_account_pre_balance", transfer.funding_account_balance.as_ref().map(|x| x.pre_balance).unwrap_or(0))
                 .set("funding_account_post_balance", transfer.funding_account_balance.as_ref().map(|x| x.post_balance).unwrap_or(0))
@@ -159,7 +400,11 @@ fn parse_system_program_instruction<'a>(
                 .set("recipient_account_pre_balance", transfer.recipient_account_balance.as_ref().map(|x| x.pre_balance).unwrap_or(0))
                 .set("recipient_account_post_balance", transfer.recipient_account_balance.as_ref().map(|x| x.post_balance).unwrap_or(0))
                 .set("lamports", transfer.lamports)
-                .set("transfer_type", "unknown")
+                .set("transfer_type", "unknown");
+            if let Some(usd_value) = usd_prices.get_lamports_usd_value(transfer.lamports) {
+                row.set("usd_value", usd_value.to_string());
+            }
+            row
         },
         Some(system_program_event::Event::CreateAccountWithSeed(create_account_with_seed)) => {
             tables.create_row("system_program_create_account_with_seed_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
@@ -215,7 +460,7 @@ fn parse_system_program_instruction<'a>(
                 .set("owner", assign_with_seed.owner)
         },
         Some(system_program_event::Event::TransferWithSeed(transfer_with_seed)) => {
-            tables.create_row("system_program_transfer_with_seed_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            let row = tables.create_row("system_program_transfer_with_seed_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("funding_account", transfer_with_seed.funding_account)
                 .set("funding_account_pre_balance", transfer_with_seed.funding_account_balance.as_ref().map(|x| x.pre_balance).unwrap_or(0))
                 .set("funding_account_post_balance", transfer_with_seed.funding_account_balance.as_ref().map(|x| x.post_balance).unwrap_or(0))
@@ -226,7 +471,11 @@ fn parse_system_program_instruction<'a>(
                 .set("lamports", transfer_with_seed.lamports)
                 .set("from_seed", transfer_with_seed.from_seed)
                 .set("from_owner", transfer_with_seed.from_owner)
-                .set("transfer_type", "unknown")
+                .set("transfer_type", "unknown");
+            if let Some(usd_value) = usd_prices.get_lamports_usd_value(transfer_with_seed.lamports) {
+                row.set("usd_value", usd_value.to_string());
+            }
+            row
         },
         Some(system_program_event::Event::UpgradeNonceAccount(upgrade_nonce_account)) => {
             tables.create_row("system_program_upgrade_nonce_account_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
@@ -237,15 +486,222 @@ fn parse_system_program_instruction<'a>(
     Ok(Some(row))
 }
 
+fn insert_compute_budget_event<'a>(
+    instruction: &IndexedInstruction,
+    event: &ComputeBudgetEvent,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> &'a mut Row {
+    let row = tables.create_row("compute_budget_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())]);
+    match *event {
+        ComputeBudgetEvent::RequestHeapFrame { bytes } => {
+            row.set("type", "request_heap_frame").set("heap_frame_bytes", bytes);
+        },
+        ComputeBudgetEvent::SetComputeUnitLimit { units } => {
+            row.set("type", "set_compute_unit_limit").set("compute_unit_limit", units);
+        },
+        ComputeBudgetEvent::SetComputeUnitPrice { micro_lamports } => {
+            row.set("type", "set_compute_unit_price").set("compute_unit_price", micro_lamports);
+        },
+        ComputeBudgetEvent::SetLoadedAccountsDataSizeLimit { bytes } => {
+            row.set("type", "set_loaded_accounts_data_size_limit").set("loaded_accounts_data_size_limit", bytes);
+        },
+    }
+    set_parent_instruction_columns(instruction, row);
+    row
+}
+
+fn parse_address_lookup_table_instruction<'a>(
+    instruction: &IndexedInstruction,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let event = match address_lookup_tables::parse_instruction(instruction)? {
+        Some(event) => event,
+        None => return Ok(None),
+    };
+    let row = tables.create_row("address_lookup_table_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+        .set("type", event.instruction_type)
+        .set("lookup_table", event.lookup_table)
+        .set("authority", event.authority)
+        .set("payer", event.payer.unwrap_or_default())
+        .set("recipient", event.recipient.unwrap_or_default())
+        .set("new_addresses", to_array_value(&event.new_addresses));
+    if let Some(recent_slot) = event.recent_slot {
+        row.set("recent_slot", recent_slot);
+    }
+    Ok(Some(row))
+}
+
+fn parse_associated_token_account_instruction<'a>(
+    instruction: &IndexedInstruction,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let event = match associated_token_accounts::parse_instruction(instruction)? {
+        Some(event) => event,
+        None => return Ok(None),
+    };
+    let row = tables.create_row("associated_token_account_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+        .set("type", event.instruction_type)
+        .set("wallet", event.wallet)
+        .set("mint", event.mint)
+        .set("associated_token_account", event.associated_token_account)
+        .set("payer", event.payer.unwrap_or_default())
+        .set("token_program", event.token_program)
+        .set("already_existed", event.already_existed)
+        .set("nested_token_account", event.nested_token_account.unwrap_or_default())
+        .set("owner_mint", event.owner_mint.unwrap_or_default());
+    Ok(Some(row))
+}
+
+fn parse_bpf_loader_upgradeable_instruction<'a>(
+    instruction: &IndexedInstruction,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let event = match bpf_loader_upgradeable::parse_instruction(instruction)? {
+        // Writes are aggregated by buffer for the whole transaction.
+        Some(event) if event.instruction_type == "write" => return Ok(None),
+        Some(event) => event,
+        None => return Ok(None),
+    };
+    let row = tables.create_row("program_deploy_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+        .set("type", event.instruction_type)
+        .set("program_id", event.program_id.unwrap_or_default())
+        .set("programdata", event.programdata.unwrap_or_default())
+        .set("buffer", event.buffer.unwrap_or_default())
+        .set("account", event.account.unwrap_or_default())
+        .set("authority", event.authority.unwrap_or_default())
+        .set("new_authority", event.new_authority.unwrap_or_default())
+        .set("payer", event.payer.unwrap_or_default())
+        .set("recipient", event.recipient.unwrap_or_default());
+    if let Some(data_length) = event.data_length {
+        row.set("data_length", data_length);
+    }
+    Ok(Some(row))
+}
+
+fn parse_memo_instruction<'a>(
+    instruction: &IndexedInstruction,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let event = match memo::parse_instruction(instruction) {
+        Some(event) => event,
+        None => return Ok(None),
+    };
+    let row = tables.create_row("memo_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+        .set("version", event.version)
+        .set("memo", event.memo)
+        .set("is_lossy", event.is_lossy)
+        .set("number_of_signers", event.signers.len().to_string())
+        .set("signers", to_array_value(&event.signers));
+    Ok(Some(row))
+}
+
+fn parse_stake_program_instruction<'a>(
+    instruction: &IndexedInstruction,
+    usd_prices: &UsdPrices,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let row = match stake_program::parse_instruction(instruction)? {
+        Some(StakeProgramEvent::Initialize(initialize)) => {
+            tables.create_row("stake_program_initialize_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("type", initialize.instruction_type)
+                .set("stake_account", initialize.stake_account)
+                .set("staker", initialize.staker)
+                .set("withdrawer", initialize.withdrawer)
+                .set("lockup_unix_timestamp", initialize.lockup_unix_timestamp)
+                .set("lockup_epoch", initialize.lockup_epoch)
+                .set("lockup_custodian", initialize.lockup_custodian)
+        },
+        Some(StakeProgramEvent::Authorize(authorize)) => {
+            tables.create_row("stake_program_authorize_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("type", authorize.instruction_type)
+                .set("stake_account", authorize.stake_account)
+                .set("authority", authorize.authority)
+                .set("new_authority", authorize.new_authority)
+                .set("authority_type", authorize.authority_type)
+        },
+        Some(StakeProgramEvent::DelegateStake(delegate_stake)) => {
+            tables.create_row("stake_program_delegate_stake_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("stake_account", delegate_stake.stake_account)
+                .set("vote_account", delegate_stake.vote_account)
+                .set("stake_authority", delegate_stake.stake_authority)
+        },
+        Some(StakeProgramEvent::Split(split)) => {
+            let row = tables.create_row("stake_program_split_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("stake_account", split.stake_account)
+                .set("split_stake_account", split.split_stake_account)
+                .set("stake_authority", split.stake_authority)
+                .set("lamports", split.lamports);
+            if let Some(usd_value) = usd_prices.get_lamports_usd_value(split.lamports) {
+                row.set("usd_value", usd_value.to_string());
+            }
+            row
+        },
+        Some(StakeProgramEvent::Withdraw(withdraw)) => {
+            let row = tables.create_row("stake_program_withdraw_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("stake_account", withdraw.stake_account)
+                .set("recipient_account", withdraw.recipient_account)
+                .set("withdraw_authority", withdraw.withdraw_authority)
+                .set("lamports", withdraw.lamports);
+            if let Some(usd_value) = usd_prices.get_lamports_usd_value(withdraw.lamports) {
+                row.set("usd_value", usd_value.to_string());
+            }
+            row
+        },
+        Some(StakeProgramEvent::Deactivate(deactivate)) => {
+            tables.create_row("stake_program_deactivate_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("stake_account", deactivate.stake_account)
+                .set("stake_authority", deactivate.stake_authority)
+        },
+        Some(StakeProgramEvent::SetLockup(set_lockup)) => {
+            let row = tables.create_row("stake_program_set_lockup_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("type", set_lockup.instruction_type)
+                .set("stake_account", set_lockup.stake_account)
+                .set("authority", set_lockup.authority)
+                .set("custodian", set_lockup.custodian.unwrap_or_default());
+            if let Some(unix_timestamp) = set_lockup.unix_timestamp {
+                row.set("unix_timestamp", unix_timestamp);
+            }
+            if let Some(epoch) = set_lockup.epoch {
+                row.set("epoch", epoch);
+            }
+            row
+        },
+        Some(StakeProgramEvent::Merge(merge)) => {
+            tables.create_row("stake_program_merge_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("destination_stake_account", merge.destination_stake_account)
+                .set("source_stake_account", merge.source_stake_account)
+                .set("stake_authority", merge.stake_authority)
+        },
+        None => return Ok(None),
+    };
+    Ok(Some(row))
+}
+
 fn parse_spl_token_instruction<'a>(
     instruction: &IndexedInstruction,
     context: &TransactionContext,
+    stores: &Stores,
+    mint_decimals: &MintDecimals,
     tables: &'a mut Tables,
     slot: u64,
     transaction_index: u32,
 ) -> Result<Option<&'a mut Row>, Error> {
+    let Stores { mints: mint_stores, usd_prices, .. } = stores;
     let row = match spl_token_substream::parse_instruction(&instruction.instruction, context)? {
         Some(spl_token_event::Event::InitializeMint(initialize_mint)) => {
+            insert_mint(&initialize_mint.mint, slot, mint_stores, tables);
             let row = tables.create_row("spl_token_initialize_mint_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("mint", &initialize_mint.mint)
                 .set("decimals", initialize_mint.decimals)
@@ -269,7 +725,7 @@ fn parse_spl_token_instruction<'a>(
                 .set("m", initialize_multisig.m)
         },
         Some(spl_token_event::Event::Transfer(transfer)) => {
-            tables.create_row("spl_token_transfer_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            let row = tables.create_row("spl_token_transfer_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("source_address", &transfer.source.as_ref().unwrap().address)
                 .set("source_owner", &transfer.source.as_ref().unwrap().owner)
                 .set("destination_address", &transfer.destination.as_ref().unwrap().address)
@@ -279,7 +735,15 @@ fn parse_spl_token_instruction<'a>(
                 .set("amount", transfer.amount)
                 .set("source_pre_balance", transfer.source.as_ref().unwrap().pre_balance.unwrap_or(0))
                 .set("destination_pre_balance", transfer.source.as_ref().unwrap().pre_balance.unwrap_or(0))
-                .set("transfer_type", "unknown")
+                .set("transfer_type", "unknown");
+            let mint = &transfer.source.as_ref().unwrap().mint;
+            if let Some(ui_amount) = mint_decimals.get_ui_amount(mint, transfer.amount) {
+                row.set("ui_amount", ui_amount);
+            }
+            if let Some(usd_value) = usd_prices.get_usd_value(mint, transfer.amount, mint_decimals.get(mint)) {
+                row.set("usd_value", usd_value.to_string());
+            }
+            row
         },
         Some(spl_token_event::Event::Approve(approve)) => {
             tables.create_row("spl_token_approve_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
@@ -296,6 +760,10 @@ fn parse_spl_token_instruction<'a>(
                 .set("mint", &revoke.source.as_ref().unwrap().mint)
         },
         Some(spl_token_event::Event::SetAuthority(set_authority)) => {
+            match AuthorityType::from_i32(set_authority.authority_type).unwrap() {
+                AuthorityType::MintTokens | AuthorityType::FreezeAccount => insert_mint(&set_authority.mint, slot, mint_stores, tables),
+                _ => (),
+            }
             let row = tables.create_row("spl_token_set_authority_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("mint", &set_authority.mint)
                 .set("authority_type", AuthorityType::from_i32(set_authority.authority_type).unwrap().as_str_name());
@@ -306,22 +774,32 @@ fn parse_spl_token_instruction<'a>(
             row
         },
         Some(spl_token_event::Event::MintTo(mint_to)) => {
-            tables.create_row("spl_token_mint_to_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            insert_mint(&mint_to.mint, slot, mint_stores, tables);
+            let row = tables.create_row("spl_token_mint_to_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("destination_address", &mint_to.destination.as_ref().unwrap().address)
                 .set("destination_owner", &mint_to.destination.as_ref().unwrap().owner)
                 .set("mint", &mint_to.mint)
                 .set("mint_authority", &mint_to.mint_authority)
                 .set("amount", mint_to.amount)
-                .set("destination_pre_balance", mint_to.destination.unwrap().pre_balance.unwrap_or(0))
+                .set("destination_pre_balance", mint_to.destination.unwrap().pre_balance.unwrap_or(0));
+            if let Some(ui_amount) = mint_decimals.get_ui_amount(&mint_to.mint, mint_to.amount) {
+                row.set("ui_amount", ui_amount);
+            }
+            row
         },
         Some(spl_token_event::Event::Burn(burn)) => {
-            tables.create_row("spl_token_burn_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            insert_mint(&burn.source.as_ref().unwrap().mint, slot, mint_stores, tables);
+            let row = tables.create_row("spl_token_burn_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("source_address", &burn.source.as_ref().unwrap().address)
                 .set("source_owner", &burn.source.as_ref().unwrap().owner)
                 .set("mint", &burn.source.as_ref().unwrap().mint)
                 .set("amount", burn.amount)
                 .set("authority", &burn.authority)
-                .set("source_pre_balance", burn.source.unwrap().pre_balance.unwrap_or(0))
+                .set("source_pre_balance", burn.source.as_ref().unwrap().pre_balance.unwrap_or(0));
+            if let Some(ui_amount) = mint_decimals.get_ui_amount(&burn.source.as_ref().unwrap().mint, burn.amount) {
+                row.set("ui_amount", ui_amount);
+            }
+            row
         },
         Some(spl_token_event::Event::CloseAccount(close_account)) => {
             tables.create_row("spl_token_close_account_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
@@ -360,16 +838,38 @@ fn parse_spl_token_instruction<'a>(
     Ok(Some(row))
 }
 
+/// Sets the UI amounts and the USD value of a swap row.
+fn set_swap_value_columns(row: &mut Row, mint_in: &str, amount_in: u64, mint_out: &str, amount_out: u64, mint_decimals: &MintDecimals, usd_prices: &UsdPrices) {
+    if let Some(ui_amount_in) = mint_decimals.get_ui_amount(mint_in, amount_in) {
+        row.set("ui_amount_in", ui_amount_in);
+    }
+    if let Some(ui_amount_out) = mint_decimals.get_ui_amount(mint_out, amount_out) {
+        row.set("ui_amount_out", ui_amount_out);
+    }
+    let usd_value = usd_prices.get_usd_value(mint_in, amount_in, mint_decimals.get(mint_in))
+        .or_else(|| usd_prices.get_usd_value(mint_out, amount_out, mint_decimals.get(mint_out)));
+    if let Some(usd_value) = usd_value {
+        row.set("usd_value", usd_value.to_string());
+    }
+}
+
 fn parse_raydium_amm_instruction<'a>(
     instruction: &IndexedInstruction,
     context: &TransactionContext,
+    stores: &Stores,
+    mint_decimals: &MintDecimals,
     tables: &'a mut Tables,
     slot: u64,
     transaction_index: u32,
 ) -> Result<Option<&'a mut Row>, Error> {
+    let Stores { mints: mint_stores, usd_prices, raydium_pools, pumpfun_tokens, .. } = stores;
     let row = match raydium_amm_substream::parse_instruction(&instruction.instruction, context).map_err(|x| anyhow!(x))? {
         Some(raydium_amm_event::Event::Swap(swap)) => {
-            tables.create_row("raydium_amm_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            if raydium_pools.is_new(&swap.amm, slot) {
+                insert_raydium_pool(&swap.amm, slot, raydium_pools, mint_stores, tables);
+            }
+            insert_raydium_pool_state(&swap.amm, slot, raydium_pools, mint_stores, usd_prices, tables);
+            let row = tables.create_row("raydium_amm_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("amm", &swap.amm)
                 .set("user", &swap.user)
                 .set("amount_in", swap.amount_in)
@@ -380,10 +880,20 @@ fn parse_raydium_amm_instruction<'a>(
                 .set("pool_pc_amount", swap.pool_pc_amount.unwrap_or(0))
                 .set("pool_coin_amount", swap.pool_coin_amount.unwrap_or(0))
                 .set("user_pre_balance_in", swap.user_pre_balance_in.unwrap_or(0))
-                .set("user_pre_balance_out", swap.user_pre_balance_out.unwrap_or(0))
+                .set("user_pre_balance_out", swap.user_pre_balance_out.unwrap_or(0));
+            set_swap_value_columns(row, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out, mint_decimals, usd_prices);
+            set_swap_limit_columns(instruction, row);
+            raydium_pools.set_pool_columns(&swap.amm, slot, mint_stores, row);
+            row
         }
         Some(raydium_amm_event::Event::Initialize(initialize)) => {
-            tables.create_row("raydium_amm_initialize_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            insert_raydium_pool(&initialize.amm, slot, raydium_pools, mint_stores, tables);
+            for mint in [&initialize.coin_mint, &initialize.pc_mint] {
+                if pumpfun_tokens.contains(mint) {
+                    insert_pumpfun_token(mint, slot, pumpfun_tokens, raydium_pools, tables);
+                }
+            }
+            let row = tables.create_row("raydium_amm_initialize_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("amm", &initialize.amm)
                 .set("user", &initialize.user)
                 .set("pc_init_amount", initialize.pc_init_amount)
@@ -393,10 +903,21 @@ fn parse_raydium_amm_instruction<'a>(
                 .set("coin_mint", &initialize.coin_mint)
                 .set("lp_mint", &initialize.lp_mint)
                 .set("user_pc_pre_balance", initialize.user_pc_pre_balance.unwrap_or(0))
-                .set("user_coin_pre_balance", initialize.user_coin_pre_balance.unwrap_or(0))
+                .set("user_coin_pre_balance", initialize.user_coin_pre_balance.unwrap_or(0));
+            if let Some(open_time) = get_open_time(instruction) {
+                row.set("open_time", open_time);
+            }
+            if let Some((market_program_id, market)) = get_market(instruction) {
+                row
+                    .set("market_program_id", market_program_id)
+                    .set("market", market);
+            }
+            row
         },
         Some(raydium_amm_event::Event::Deposit(deposit)) => {
-            tables.create_row("raydium_amm_deposit_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            insert_raydium_pool(&deposit.amm, slot, raydium_pools, mint_stores, tables);
+            insert_raydium_pool_state(&deposit.amm, slot, raydium_pools, mint_stores, usd_prices, tables);
+            let row = tables.create_row("raydium_amm_deposit_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("amm", &deposit.amm)
                 .set("user", &deposit.user)
                 .set("pc_amount", deposit.pc_amount)
@@ -408,10 +929,14 @@ fn parse_raydium_amm_instruction<'a>(
                 .set("coin_mint", &deposit.coin_mint)
                 .set("lp_mint", &deposit.lp_mint)
                 .set("user_pc_pre_balance", deposit.user_pc_pre_balance.unwrap_or(0))
-                .set("user_coin_pre_balance", deposit.user_coin_pre_balance.unwrap_or(0))
+                .set("user_coin_pre_balance", deposit.user_coin_pre_balance.unwrap_or(0));
+            raydium_pools.set_pool_columns(&deposit.amm, slot, mint_stores, row);
+            row
         },
         Some(raydium_amm_event::Event::Withdraw(withdraw)) => {
-            tables.create_row("raydium_amm_withdraw_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            insert_raydium_pool(&withdraw.amm, slot, raydium_pools, mint_stores, tables);
+            insert_raydium_pool_state(&withdraw.amm, slot, raydium_pools, mint_stores, usd_prices, tables);
+            let row = tables.create_row("raydium_amm_withdraw_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("amm", &withdraw.amm)
                 .set("user", &withdraw.user)
                 .set("pc_amount", withdraw.pc_amount)
@@ -423,7 +948,9 @@ fn parse_raydium_amm_instruction<'a>(
                 .set("coin_mint", &withdraw.coin_mint)
                 .set("lp_mint", &withdraw.lp_mint)
                 .set("user_pc_pre_balance", withdraw.user_pc_pre_balance.unwrap_or(0))
-                .set("user_coin_pre_balance", withdraw.user_coin_pre_balance.unwrap_or(0))
+                .set("user_coin_pre_balance", withdraw.user_coin_pre_balance.unwrap_or(0));
+            raydium_pools.set_pool_columns(&withdraw.amm, slot, mint_stores, row);
+            row
         },
         Some(raydium_amm_event::Event::WithdrawPnl(withdraw_pnl)) => {
             tables.create_row("raydium_amm_withdraw_pnl_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
@@ -434,7 +961,549 @@ fn parse_raydium_amm_instruction<'a>(
                 .set("pc_mint", withdraw_pnl.pc_mint.unwrap_or("".to_string()))
                 .set("coin_mint", withdraw_pnl.coin_mint.unwrap_or("".to_string()))
         }
-        _ => return Ok(None),
+        _ => return Ok(insert_raydium_amm_other_event(instruction, tables, slot, transaction_index)),
+    };
+    Ok(Some(row))
+}
+
+fn parse_raydium_clmm_instruction<'a>(
+    instruction: &IndexedInstruction,
+    context: &TransactionContext,
+    mint_decimals: &MintDecimals,
+    usd_prices: &UsdPrices,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let row = match raydium_clmm::parse_instruction(instruction, context)? {
+        Some(RaydiumClmmEvent::Swap(swap)) => {
+            let row = tables.create_row("raydium_clmm_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("pool_state", &swap.pool_state)
+                .set("user", &swap.user)
+                .set("amount_in", swap.amount_in)
+                .set("amount_out", swap.amount_out)
+                .set("mint_in", &swap.mint_in)
+                .set("mint_out", &swap.mint_out)
+                .set("is_base_input", swap.is_base_input)
+                .set("other_amount_threshold", swap.other_amount_threshold)
+                .set("sqrt_price_limit_x64", swap.sqrt_price_limit_x64.to_string());
+            if let (Some(sqrt_price_x64), Some(liquidity), Some(tick)) = (swap.sqrt_price_x64, swap.liquidity, swap.tick) {
+                row
+                    .set("sqrt_price_x64", sqrt_price_x64.to_string())
+                    .set("liquidity", liquidity.to_string())
+                    .set("tick", tick);
+            }
+            set_swap_value_columns(row, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out, mint_decimals, usd_prices);
+            row
+        },
+        Some(RaydiumClmmEvent::CreatePool(create_pool)) => {
+            tables.create_row("raydium_clmm_create_pool_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("pool_state", create_pool.pool_state)
+                .set("creator", create_pool.creator)
+                .set("amm_config", create_pool.amm_config)
+                .set("token_mint_0", create_pool.token_mint_0)
+                .set("token_mint_1", create_pool.token_mint_1)
+                .set("token_vault_0", create_pool.token_vault_0)
+                .set("token_vault_1", create_pool.token_vault_1)
+                .set("sqrt_price_x64", create_pool.sqrt_price_x64.to_string())
+                .set("open_time", create_pool.open_time)
+        },
+        Some(RaydiumClmmEvent::OpenPosition(open_position)) => {
+            tables.create_row("raydium_clmm_open_position_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("pool_state", open_position.pool_state)
+                .set("owner", open_position.owner)
+                .set("position_nft_mint", open_position.position_nft_mint)
+                .set("personal_position", open_position.personal_position)
+                .set("tick_lower_index", open_position.tick_lower_index)
+                .set("tick_upper_index", open_position.tick_upper_index)
+                .set("liquidity", open_position.liquidity.to_string())
+                .set("amount_0", open_position.amount_0)
+                .set("amount_1", open_position.amount_1)
+                .set("amount_0_max", open_position.amount_0_max)
+                .set("amount_1_max", open_position.amount_1_max)
+                .set("token_mint_0", open_position.token_mint_0.unwrap_or_default())
+                .set("token_mint_1", open_position.token_mint_1.unwrap_or_default())
+        },
+        Some(RaydiumClmmEvent::ClosePosition(close_position)) => {
+            tables.create_row("raydium_clmm_close_position_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("owner", close_position.owner)
+                .set("position_nft_mint", close_position.position_nft_mint)
+                .set("personal_position", close_position.personal_position)
+        },
+        Some(RaydiumClmmEvent::IncreaseLiquidity(increase_liquidity)) => {
+            tables.create_row("raydium_clmm_increase_liquidity_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("pool_state", increase_liquidity.pool_state)
+                .set("owner", increase_liquidity.owner)
+                .set("personal_position", increase_liquidity.personal_position)
+                .set("liquidity", increase_liquidity.liquidity.to_string())
+                .set("amount_0", increase_liquidity.amount_0)
+                .set("amount_1", increase_liquidity.amount_1)
+                .set("amount_0_max", increase_liquidity.amount_0_limit)
+                .set("amount_1_max", increase_liquidity.amount_1_limit)
+                .set("token_mint_0", increase_liquidity.token_mint_0.unwrap_or_default())
+                .set("token_mint_1", increase_liquidity.token_mint_1.unwrap_or_default())
+        },
+        Some(RaydiumClmmEvent::DecreaseLiquidity(decrease_liquidity)) => {
+            tables.create_row("raydium_clmm_decrease_liquidity_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("pool_state", decrease_liquidity.pool_state)
+                .set("owner", decrease_liquidity.owner)
+                .set("personal_position", decrease_liquidity.personal_position)
+                .set("liquidity", decrease_liquidity.liquidity.to_string())
+                .set("amount_0", decrease_liquidity.amount_0)
+                .set("amount_1", decrease_liquidity.amount_1)
+                .set("amount_0_min", decrease_liquidity.amount_0_limit)
+                .set("amount_1_min", decrease_liquidity.amount_1_limit)
+                .set("token_mint_0", decrease_liquidity.token_mint_0.unwrap_or_default())
+                .set("token_mint_1", decrease_liquidity.token_mint_1.unwrap_or_default())
+        },
+        Some(RaydiumClmmEvent::Collect(collect)) => {
+            tables.create_row("raydium_clmm_collect_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("type", collect.collect_type)
+                .set("pool_state", collect.pool_state)
+                .set("user", collect.user)
+                .set("amount_0", collect.amount_0)
+                .set("amount_1", collect.amount_1)
+                .set("token_mint_0", collect.token_mint_0.unwrap_or_default())
+                .set("token_mint_1", collect.token_mint_1.unwrap_or_default())
+        },
+        None => return Ok(None),
+    };
+    Ok(Some(row))
+}
+
+fn parse_raydium_cpmm_instruction<'a>(
+    instruction: &IndexedInstruction,
+    context: &TransactionContext,
+    tokens: &TransactionTokens,
+    usd_prices: &UsdPrices,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let TransactionTokens { mint_decimals, post_token_balances } = tokens;
+    let row = match raydium_cpmm::parse_instruction(instruction, context, post_token_balances)? {
+        Some(RaydiumCpmmEvent::Swap(swap)) => {
+            let row = tables.create_row("raydium_cpmm_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("pool_state", &swap.pool_state)
+                .set("user", &swap.user)
+                .set("amount_in", swap.amount_in)
+                .set("amount_out", swap.amount_out)
+                .set("mint_in", &swap.mint_in)
+                .set("mint_out", &swap.mint_out)
+                .set("swap_type", swap.swap_type);
+            match swap.swap_type {
+                "swap_base_input" => row.set("minimum_amount_out", swap.limit_amount),
+                _ => row.set("max_amount_in", swap.limit_amount),
+            };
+            if let Some(input_vault_balance) = swap.input_vault_balance {
+                row.set("input_vault_balance", input_vault_balance);
+            }
+            if let Some(output_vault_balance) = swap.output_vault_balance {
+                row.set("output_vault_balance", output_vault_balance);
+            }
+            set_swap_value_columns(row, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out, mint_decimals, usd_prices);
+            row
+        },
+        Some(RaydiumCpmmEvent::Initialize(initialize)) => {
+            let row = tables.create_row("raydium_cpmm_initialize_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("pool_state", initialize.pool_state)
+                .set("creator", initialize.creator)
+                .set("amm_config", initialize.amm_config)
+                .set("token_0_mint", initialize.token_0_mint)
+                .set("token_1_mint", initialize.token_1_mint)
+                .set("lp_mint", initialize.lp_mint)
+                .set("init_amount_0", initialize.init_amount_0)
+                .set("init_amount_1", initialize.init_amount_1)
+                .set("open_time", initialize.open_time);
+            if let Some(token_0_vault_balance) = initialize.token_0_vault_balance {
+                row.set("token_0_vault_balance", token_0_vault_balance);
+            }
+            if let Some(token_1_vault_balance) = initialize.token_1_vault_balance {
+                row.set("token_1_vault_balance", token_1_vault_balance);
+            }
+            row
+        },
+        Some(RaydiumCpmmEvent::Deposit(deposit)) => {
+            let row = tables.create_row("raydium_cpmm_deposit_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("maximum_amount_0", deposit.amount_0_limit)
+                .set("maximum_amount_1", deposit.amount_1_limit);
+            set_raydium_cpmm_liquidity_columns(&deposit, row);
+            row
+        },
+        Some(RaydiumCpmmEvent::Withdraw(withdraw)) => {
+            let row = tables.create_row("raydium_cpmm_withdraw_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("minimum_amount_0", withdraw.amount_0_limit)
+                .set("minimum_amount_1", withdraw.amount_1_limit);
+            set_raydium_cpmm_liquidity_columns(&withdraw, row);
+            row
+        },
+        None => return Ok(None),
+    };
+    Ok(Some(row))
+}
+
+fn set_raydium_cpmm_liquidity_columns(event: &raydium_cpmm::LiquidityEvent, row: &mut Row) {
+    row
+        .set("pool_state", &event.pool_state)
+        .set("owner", &event.owner)
+        .set("token_0_mint", &event.token_0_mint)
+        .set("token_1_mint", &event.token_1_mint)
+        .set("lp_mint", &event.lp_mint)
+        .set("lp_token_amount", event.lp_token_amount)
+        .set("amount_0", event.amount_0)
+        .set("amount_1", event.amount_1);
+    if let Some(token_0_vault_balance) = event.token_0_vault_balance {
+        row.set("token_0_vault_balance", token_0_vault_balance);
+    }
+    if let Some(token_1_vault_balance) = event.token_1_vault_balance {
+        row.set("token_1_vault_balance", token_1_vault_balance);
+    }
+}
+
+fn parse_whirlpool_instruction<'a>(
+    instruction: &IndexedInstruction,
+    context: &TransactionContext,
+    mint_decimals: &MintDecimals,
+    usd_prices: &UsdPrices,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let row = match whirlpool::parse_instruction(instruction, context)? {
+        Some(WhirlpoolEvent::Swap(swap)) => {
+            let row = tables.create_row("whirlpool_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("whirlpool", &swap.whirlpool)
+                .set("user", &swap.user)
+                .set("amount_in", swap.amount_in)
+                .set("amount_out", swap.amount_out)
+                .set("mint_in", &swap.mint_in)
+                .set("mint_out", &swap.mint_out)
+                .set("a_to_b", swap.a_to_b)
+                .set("amount_specified_is_input", swap.amount_specified_is_input)
+                .set("other_amount_threshold", swap.other_amount_threshold)
+                .set("sqrt_price_limit", swap.sqrt_price_limit.to_string());
+            if let (Some(pre_sqrt_price), Some(post_sqrt_price)) = (swap.pre_sqrt_price, swap.post_sqrt_price) {
+                row
+                    .set("pre_sqrt_price", pre_sqrt_price.to_string())
+                    .set("post_sqrt_price", post_sqrt_price.to_string());
+            }
+            set_swap_value_columns(row, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out, mint_decimals, usd_prices);
+            row
+        },
+        Some(WhirlpoolEvent::TwoHopSwap(swap_one, swap_two)) => {
+            let row = tables.create_row("whirlpool_two_hop_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("whirlpool_one", &swap_one.whirlpool)
+                .set("whirlpool_two", &swap_two.whirlpool)
+                .set("user", &swap_one.user)
+                .set("amount_in", swap_one.amount_in)
+                .set("amount_intermediate", swap_one.amount_out)
+                .set("amount_out", swap_two.amount_out)
+                .set("mint_in", &swap_one.mint_in)
+                .set("mint_intermediate", &swap_one.mint_out)
+                .set("mint_out", &swap_two.mint_out)
+                .set("a_to_b_one", swap_one.a_to_b)
+                .set("a_to_b_two", swap_two.a_to_b)
+                .set("amount_specified_is_input", swap_one.amount_specified_is_input)
+                .set("other_amount_threshold", swap_one.other_amount_threshold)
+                .set("sqrt_price_limit_one", swap_one.sqrt_price_limit.to_string())
+                .set("sqrt_price_limit_two", swap_two.sqrt_price_limit.to_string());
+            if let (Some(post_sqrt_price_one), Some(post_sqrt_price_two)) = (swap_one.post_sqrt_price, swap_two.post_sqrt_price) {
+                row
+                    .set("post_sqrt_price_one", post_sqrt_price_one.to_string())
+                    .set("post_sqrt_price_two", post_sqrt_price_two.to_string());
+            }
+            set_swap_value_columns(row, &swap_one.mint_in, swap_one.amount_in, &swap_two.mint_out, swap_two.amount_out, mint_decimals, usd_prices);
+            row
+        },
+        Some(WhirlpoolEvent::InitializePool(initialize_pool)) => {
+            tables.create_row("whirlpool_initialize_pool_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("whirlpool", initialize_pool.whirlpool)
+                .set("whirlpools_config", initialize_pool.whirlpools_config)
+                .set("funder", initialize_pool.funder)
+                .set("token_mint_a", initialize_pool.token_mint_a)
+                .set("token_mint_b", initialize_pool.token_mint_b)
+                .set("token_vault_a", initialize_pool.token_vault_a)
+                .set("token_vault_b", initialize_pool.token_vault_b)
+                .set("tick_spacing", initialize_pool.tick_spacing)
+                .set("initial_sqrt_price", initialize_pool.initial_sqrt_price.to_string())
+        },
+        Some(WhirlpoolEvent::OpenPosition(open_position)) => {
+            tables.create_row("whirlpool_open_position_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("whirlpool", open_position.whirlpool)
+                .set("owner", open_position.owner)
+                .set("position", open_position.position)
+                .set("position_mint", open_position.position_mint)
+                .set("tick_lower_index", open_position.tick_lower_index)
+                .set("tick_upper_index", open_position.tick_upper_index)
+        },
+        Some(WhirlpoolEvent::ClosePosition(close_position)) => {
+            tables.create_row("whirlpool_close_position_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("position_authority", close_position.position_authority)
+                .set("receiver", close_position.receiver)
+                .set("position", close_position.position)
+                .set("position_mint", close_position.position_mint)
+        },
+        Some(WhirlpoolEvent::IncreaseLiquidity(increase_liquidity)) => {
+            tables.create_row("whirlpool_increase_liquidity_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("whirlpool", increase_liquidity.whirlpool)
+                .set("position_authority", increase_liquidity.position_authority)
+                .set("position", increase_liquidity.position)
+                .set("liquidity", increase_liquidity.liquidity.to_string())
+                .set("token_a_amount", increase_liquidity.token_a_amount)
+                .set("token_b_amount", increase_liquidity.token_b_amount)
+                .set("token_max_a", increase_liquidity.token_a_limit)
+                .set("token_max_b", increase_liquidity.token_b_limit)
+                .set("token_mint_a", increase_liquidity.token_mint_a.unwrap_or_default())
+                .set("token_mint_b", increase_liquidity.token_mint_b.unwrap_or_default())
+        },
+        Some(WhirlpoolEvent::DecreaseLiquidity(decrease_liquidity)) => {
+            tables.create_row("whirlpool_decrease_liquidity_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("whirlpool", decrease_liquidity.whirlpool)
+                .set("position_authority", decrease_liquidity.position_authority)
+                .set("position", decrease_liquidity.position)
+                .set("liquidity", decrease_liquidity.liquidity.to_string())
+                .set("token_a_amount", decrease_liquidity.token_a_amount)
+                .set("token_b_amount", decrease_liquidity.token_b_amount)
+                .set("token_min_a", decrease_liquidity.token_a_limit)
+                .set("token_min_b", decrease_liquidity.token_b_limit)
+                .set("token_mint_a", decrease_liquidity.token_mint_a.unwrap_or_default())
+                .set("token_mint_b", decrease_liquidity.token_mint_b.unwrap_or_default())
+        },
+        Some(WhirlpoolEvent::Collect(collect)) => {
+            let row = tables.create_row("whirlpool_collect_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("type", collect.collect_type)
+                .set("whirlpool", collect.whirlpool)
+                .set("position_authority", collect.position_authority)
+                .set("position", collect.position)
+                .set("token_a_amount", collect.token_a_amount)
+                .set("token_b_amount", collect.token_b_amount)
+                .set("token_mint_a", collect.token_mint_a.unwrap_or_default())
+                .set("token_mint_b", collect.token_mint_b.unwrap_or_default());
+            if let Some(reward_index) = collect.reward_index {
+                row.set("reward_index", reward_index);
+            }
+            row
+        },
+        None => return Ok(None),
+    };
+    Ok(Some(row))
+}
+
+fn parse_meteora_dlmm_instruction<'a>(
+    instruction: &IndexedInstruction,
+    mint_decimals: &MintDecimals,
+    usd_prices: &UsdPrices,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let row = match meteora_dlmm::parse_instruction(instruction)? {
+        Some(MeteoraDlmmEvent::Swap(swap)) => {
+            let row = tables.create_row("meteora_dlmm_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("lb_pair", &swap.lb_pair)
+                .set("user", &swap.user)
+                .set("amount_in", swap.amount_in)
+                .set("amount_out", swap.amount_out)
+                .set("mint_in", &swap.mint_in)
+                .set("mint_out", &swap.mint_out)
+                .set("swap_for_y", swap.swap_for_y)
+                .set("start_bin_id", swap.start_bin_id)
+                .set("end_bin_id", swap.end_bin_id)
+                .set("fee", swap.fee)
+                .set("protocol_fee", swap.protocol_fee)
+                .set("host_fee", swap.host_fee)
+                .set("fee_bps", swap.fee_bps.to_string());
+            set_swap_value_columns(row, &swap.mint_in, swap.amount_in, &swap.mint_out, swap.amount_out, mint_decimals, usd_prices);
+            row
+        },
+        Some(MeteoraDlmmEvent::AddLiquidity(add_liquidity)) => {
+            let row = tables.create_row("meteora_dlmm_add_liquidity_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())]);
+            set_meteora_dlmm_liquidity_columns(&add_liquidity, row);
+            row
+        },
+        Some(MeteoraDlmmEvent::RemoveLiquidity(remove_liquidity)) => {
+            let row = tables.create_row("meteora_dlmm_remove_liquidity_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())]);
+            set_meteora_dlmm_liquidity_columns(&remove_liquidity, row);
+            row
+        },
+        Some(MeteoraDlmmEvent::LbPairCreate(lb_pair_create)) => {
+            let row = tables.create_row("meteora_dlmm_lb_pair_create_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("lb_pair", lb_pair_create.lb_pair)
+                .set("token_x", lb_pair_create.token_x)
+                .set("token_y", lb_pair_create.token_y)
+                .set("bin_step", lb_pair_create.bin_step);
+            if let Some(active_id) = lb_pair_create.active_id {
+                row.set("active_id", active_id);
+            }
+            row
+        },
+        Some(MeteoraDlmmEvent::PositionCreate(position_create)) => {
+            let row = tables.create_row("meteora_dlmm_position_create_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("lb_pair", position_create.lb_pair)
+                .set("position", position_create.position)
+                .set("owner", position_create.owner);
+            if let (Some(lower_bin_id), Some(upper_bin_id)) = (position_create.lower_bin_id, position_create.upper_bin_id) {
+                row
+                    .set("lower_bin_id", lower_bin_id)
+                    .set("upper_bin_id", upper_bin_id);
+            }
+            row
+        },
+        Some(MeteoraDlmmEvent::PositionClose(position_close)) => {
+            tables.create_row("meteora_dlmm_position_close_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("position", position_close.position)
+                .set("owner", position_close.owner)
+        },
+        Some(MeteoraDlmmEvent::Claim(claim)) => {
+            let row = tables.create_row("meteora_dlmm_claim_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("type", claim.claim_type)
+                .set("lb_pair", claim.lb_pair)
+                .set("position", claim.position)
+                .set("owner", claim.owner)
+                .set("amount_x", claim.amount_x)
+                .set("amount_y", claim.amount_y);
+            if let Some(reward_index) = claim.reward_index {
+                row.set("reward_index", reward_index);
+            }
+            row
+        },
+        None => return Ok(None),
+    };
+    Ok(Some(row))
+}
+
+fn set_meteora_dlmm_liquidity_columns(event: &meteora_dlmm::LiquidityEvent, row: &mut Row) {
+    row
+        .set("lb_pair", &event.lb_pair)
+        .set("sender", &event.sender)
+        .set("position", &event.position)
+        .set("amount_x", event.amount_x)
+        .set("amount_y", event.amount_y)
+        .set("active_bin_id", event.active_bin_id);
+    if let (Some(min_bin_id), Some(max_bin_id)) = (event.min_bin_id, event.max_bin_id) {
+        row
+            .set("min_bin_id", min_bin_id)
+            .set("max_bin_id", max_bin_id);
+    }
+}
+
+/// Inserts the trade of a whole Jupiter route. The swaps of its steps are the DEX rows whose parent instruction is this one.
+fn parse_jupiter_instruction<'a>(
+    instruction: &IndexedInstruction,
+    mint_decimals: &MintDecimals,
+    usd_prices: &UsdPrices,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let route = match jupiter::parse_instruction(instruction)? {
+        Some(route) => route,
+        None => return Ok(None),
+    };
+    let row = tables.create_row("aggregator_trades", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+        .set("aggregator", "jupiter_v6")
+        .set("type", route.route_type)
+        .set("trader", &route.trader)
+        .set("mint_in", &route.input_mint)
+        .set("amount_in", route.input_amount)
+        .set("mint_out", &route.output_mint)
+        .set("amount_out", route.output_amount)
+        .set("quoted_amount", route.quoted_amount)
+        .set("slippage_bps", route.slippage_bps)
+        .set("platform_fee_bps", route.platform_fee_bps)
+        .set("platform_fee_mint", route.platform_fee_mint.clone().unwrap_or_default())
+        .set("hops", route.hops);
+    if let Some(platform_fee_amount) = route.platform_fee_amount {
+        row.set("platform_fee_amount", platform_fee_amount);
+    }
+    set_swap_value_columns(row, &route.input_mint, route.input_amount, &route.output_mint, route.output_amount, mint_decimals, usd_prices);
+    Ok(Some(row))
+}
+
+/// Inserts an instruction of OpenBook v2 or Phoenix, and the fills of the orders it places into `order_book_fills`.
+///
+/// Fills are keyed by their index within the instruction, and share the instruction columns of their order row.
+fn parse_order_book_instruction<'a>(
+    instruction: &IndexedInstruction,
+    context: &TransactionContext,
+    mint_decimals: &MintDecimals,
+    order_book_markets: &OrderBookMarkets,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let program = if instruction.program_id() == OPENBOOK_V2_PROGRAM_ID { "openbook_v2" } else { "phoenix" };
+    let row = match order_books::parse_instruction(instruction, context)? {
+        Some(OrderBookEvent::PlaceOrder(order)) => {
+            for (fill_index, fill) in order.fills.iter().enumerate() {
+                let row = tables.create_row("order_book_fills", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string()), ("fill_index", fill_index.to_string())])
+                    .set("program", program)
+                    .set("market", &order.market)
+                    .set("maker", &fill.maker)
+                    .set("taker", &fill.taker)
+                    .set("taker_side", fill.taker_side)
+                    .set("price_in_ticks", fill.price_in_ticks)
+                    .set("size_in_lots", fill.size_in_lots);
+                if let Some(price) = order_book_markets.get_price(&order.market, fill.price_in_ticks, mint_decimals) {
+                    row.set("price", price.to_string());
+                }
+                if let Some(size) = order_book_markets.get_size(&order.market, fill.size_in_lots, mint_decimals) {
+                    row.set("size", size.to_string());
+                }
+            }
+            let row = tables.create_row("order_book_orders", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("program", program)
+                .set("type", order.instruction_type)
+                .set("market", &order.market)
+                .set("trader", &order.trader)
+                .set("side", order.side)
+                .set("order_type", order.order_type)
+                .set("size_in_lots", order.size_in_lots)
+                .set("filled_size_in_lots", order.fills.iter().map(|x| x.size_in_lots).sum::<u64>())
+                .set("number_of_fills", order.fills.len() as u32);
+            if let Some(price_in_ticks) = order.price_in_ticks {
+                row.set("price_in_ticks", price_in_ticks);
+                if let Some(price) = order_book_markets.get_price(&order.market, price_in_ticks, mint_decimals) {
+                    row.set("price", price.to_string());
+                }
+            }
+            if let Some(size) = order_book_markets.get_size(&order.market, order.size_in_lots, mint_decimals) {
+                row.set("size", size.to_string());
+            }
+            if let Some(client_order_id) = order.client_order_id {
+                row.set("client_order_id", client_order_id.to_string());
+            }
+            row
+        },
+        Some(OrderBookEvent::CancelOrder(cancel)) => {
+            let row = tables.create_row("order_book_cancels", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("program", program)
+                .set("type", cancel.instruction_type)
+                .set("market", cancel.market)
+                .set("trader", cancel.trader)
+                .set("side", cancel.side.unwrap_or_default());
+            if let Some(order_id) = cancel.order_id {
+                row.set("order_id", order_id.to_string());
+            }
+            if let Some(client_order_id) = cancel.client_order_id {
+                row.set("client_order_id", client_order_id);
+            }
+            if let (Some(orders_cancelled), Some(lots_removed)) = (cancel.orders_cancelled, cancel.lots_removed) {
+                row
+                    .set("orders_cancelled", orders_cancelled)
+                    .set("lots_removed", lots_removed);
+            }
+            row
+        },
+        Some(OrderBookEvent::SettleFunds(settle_funds)) => {
+            tables.create_row("order_book_settle_funds", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("program", program)
+                .set("market", settle_funds.market)
+                .set("owner", settle_funds.owner)
+                .set("base_amount", settle_funds.base_amount)
+                .set("quote_amount", settle_funds.quote_amount)
+        },
+        Some(OrderBookEvent::CreateMarket(_)) | None => return Ok(None),
     };
     Ok(Some(row))
 }
@@ -442,12 +1511,17 @@ fn parse_raydium_amm_instruction<'a>(
 fn parse_pumpfun_instruction<'a>(
     instruction: &IndexedInstruction,
     context: &TransactionContext,
+    stores: &Stores,
+    mint_decimals: &MintDecimals,
     tables: &'a mut Tables,
     slot: u64,
     transaction_index: u32,
 ) -> Result<Option<&'a mut Row>, Error> {
+    let Stores { mints: mint_stores, usd_prices, raydium_pools, pumpfun_tokens, pumpfun_fees, .. } = stores;
     let row = match pumpfun_substream::parse_instruction(&instruction.instruction, context)? {
         Some(pumpfun_event::Event::Create(create)) => {
+            insert_mint(&create.mint, slot, mint_stores, tables);
+            insert_pumpfun_token(&create.mint, slot, pumpfun_tokens, raydium_pools, tables);
             tables.create_row("pumpfun_create_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("user", create.user)
                 .set("name", create.name)
@@ -473,9 +1547,11 @@ fn parse_pumpfun_instruction<'a>(
                 .set("fee_basis_points", set_params.fee_basis_points)
         },
         Some(pumpfun_event::Event::Swap(swap)) => {
-            tables.create_row("pumpfun_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+            insert_pumpfun_token(&swap.mint, slot, pumpfun_tokens, raydium_pools, tables);
+            let fee_lamports = get_fee_lamports(get_transferred_fee(instruction, context), swap.sol_amount.unwrap_or(0), pumpfun_fees.get_fee_basis_points(transaction_index, instruction.index));
+            let row = tables.create_row("pumpfun_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("user", swap.user)
-                .set("mint", swap.mint)
+                .set("mint", &swap.mint)
                 .set("bonding_curve", swap.bonding_curve)
                 .set("token_amount", swap.token_amount)
                 .set("direction", swap.direction)
@@ -484,8 +1560,18 @@ fn parse_pumpfun_instruction<'a>(
                 .set("virtual_token_reserves", swap.virtual_token_reserves.unwrap_or(0))
                 .set("real_sol_reserves", swap.real_sol_reserves.unwrap_or(0))
                 .set("real_token_reserves", swap.real_token_reserves.unwrap_or(0))
+                .set("ui_sol_amount", get_ui_amount(swap.sol_amount.unwrap_or(0), SOL_DECIMALS))
+                .set("fee_lamports", fee_lamports);
+            if let Some(ui_token_amount) = mint_decimals.get_ui_amount(&swap.mint, swap.token_amount) {
+                row.set("ui_token_amount", ui_token_amount);
+            }
+            if let Some(usd_value) = usd_prices.get_lamports_usd_value(swap.sol_amount.unwrap_or(0)) {
+                row.set("usd_value", usd_value.to_string());
+            }
+            row
         },
         Some(pumpfun_event::Event::Withdraw(withdraw)) => {
+            insert_pumpfun_token(&withdraw.mint, slot, pumpfun_tokens, raydium_pools, tables);
             tables.create_row("pumpfun_withdraw_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("mint", withdraw.mint)
         },
@@ -494,15 +1580,99 @@ fn parse_pumpfun_instruction<'a>(
     Ok(Some(row))
 }
 
+fn parse_pumpswap_instruction<'a>(
+    instruction: &IndexedInstruction,
+    stores: &Stores,
+    mint_decimals: &MintDecimals,
+    tables: &'a mut Tables,
+    slot: u64,
+    transaction_index: u32,
+) -> Result<Option<&'a mut Row>, Error> {
+    let Stores { usd_prices, raydium_pools, pumpfun_tokens, .. } = stores;
+    let row = match pumpswap::parse_instruction(instruction)? {
+        Some(PumpswapEvent::CreatePool(create_pool)) => {
+            if pumpfun_tokens.contains(&create_pool.base_mint) {
+                insert_pumpfun_token(&create_pool.base_mint, slot, pumpfun_tokens, raydium_pools, tables);
+            }
+            tables.create_row("pumpswap_create_pool_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("pool", create_pool.pool)
+                .set("creator", create_pool.creator)
+                .set("index", create_pool.index)
+                .set("base_mint", create_pool.base_mint)
+                .set("quote_mint", create_pool.quote_mint)
+                .set("lp_mint", create_pool.lp_mint)
+                .set("base_amount_in", create_pool.base_amount_in)
+                .set("quote_amount_in", create_pool.quote_amount_in)
+                .set("lp_token_amount_out", create_pool.lp_token_amount_out)
+        },
+        Some(PumpswapEvent::Swap(swap)) => {
+            let row = tables.create_row("pumpswap_swap_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("user", &swap.user)
+                .set("pool", &swap.pool)
+                .set("base_mint", &swap.base_mint)
+                .set("quote_mint", &swap.quote_mint)
+                .set("direction", swap.direction)
+                .set("base_amount", swap.base_amount)
+                .set("quote_amount", swap.quote_amount)
+                .set("pool_base_token_reserves", swap.pool_base_token_reserves)
+                .set("pool_quote_token_reserves", swap.pool_quote_token_reserves)
+                .set("lp_fee", swap.lp_fee)
+                .set("protocol_fee", swap.protocol_fee)
+                .set("coin_creator", swap.coin_creator.clone().unwrap_or_default());
+            if let Some(coin_creator_fee) = swap.coin_creator_fee {
+                row.set("coin_creator_fee", coin_creator_fee);
+            }
+            if let Some(ui_base_amount) = mint_decimals.get_ui_amount(&swap.base_mint, swap.base_amount) {
+                row.set("ui_base_amount", ui_base_amount);
+            }
+            if let Some(ui_quote_amount) = mint_decimals.get_ui_amount(&swap.quote_mint, swap.quote_amount) {
+                row.set("ui_quote_amount", ui_quote_amount);
+            }
+            if let Some(usd_value) = usd_prices.get_usd_value(&swap.quote_mint, swap.quote_amount, mint_decimals.get(&swap.quote_mint)) {
+                row.set("usd_value", usd_value.to_string());
+            }
+            row
+        },
+        Some(PumpswapEvent::Deposit(deposit)) => {
+            let row = tables.create_row("pumpswap_deposit_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())]);
+            set_pumpswap_liquidity_columns(&deposit, row);
+            row
+        },
+        Some(PumpswapEvent::Withdraw(withdraw)) => {
+            let row = tables.create_row("pumpswap_withdraw_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())]);
+            set_pumpswap_liquidity_columns(&withdraw, row);
+            row
+        },
+        None => return Ok(None),
+    };
+    Ok(Some(row))
+}
+
+fn set_pumpswap_liquidity_columns(event: &pumpswap::LiquidityEvent, row: &mut Row) {
+    row
+        .set("user", &event.user)
+        .set("pool", &event.pool)
+        .set("base_mint", &event.base_mint)
+        .set("quote_mint", &event.quote_mint)
+        .set("lp_token_amount", event.lp_token_amount)
+        .set("base_amount", event.base_amount)
+        .set("quote_amount", event.quote_amount)
+        .set("pool_base_token_reserves", event.pool_base_token_reserves)
+        .set("pool_quote_token_reserves", event.pool_quote_token_reserves)
+        .set("lp_mint_supply", event.lp_mint_supply);
+}
+
 fn parse_mpl_token_metadata_instruction<'a>(
     instruction: &IndexedInstruction,
     context: &TransactionContext,
+    mint_stores: &MintStores,
     tables: &'a mut Tables,
     slot: u64,
     transaction_index: u32,
 ) -> Result<Option<&'a mut Row>, Error> {
     let row = match mpl_token_metadata_substream::parse_instruction(&instruction.instruction, context).map_err(|x| anyhow!(x))? {
         Some(mpl_token_metadata_event::Event::CreateMetadataAccountV3(create_metadata_account_v3)) => {
+            insert_mint(&create_metadata_account_v3.mint, slot, mint_stores, tables);
             let data = create_metadata_account_v3.data.unwrap();
             let row = tables.create_row("mpl_token_metadata_create_metadata_account_v3_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
                 .set("metadata", create_metadata_account_v3.metadata)
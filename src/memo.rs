@@ -0,0 +1,37 @@
+use substreams_solana_utils::pubkey::Pubkey;
+
+use crate::instruction::IndexedInstruction;
+
+/// Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo
+pub const MEMO_V1_PROGRAM_ID: Pubkey = Pubkey([5, 74, 83, 80, 248, 93, 200, 130, 214, 20, 165, 86, 114, 120, 138, 41, 109, 223, 30, 171, 171, 208, 166, 6, 120, 136, 73, 50, 244, 238, 246, 160]);
+/// MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr
+pub const MEMO_V2_PROGRAM_ID: Pubkey = Pubkey([5, 74, 83, 90, 153, 41, 33, 6, 77, 36, 232, 113, 96, 218, 56, 124, 124, 53, 181, 221, 188, 146, 187, 129, 228, 31, 168, 64, 65, 5, 68, 141]);
+
+/// A memo, whose invalid UTF-8 sequences are replaced when `is_lossy` is set.
+///
+/// Every account of the instruction must sign it, and v1 memos take no accounts.
+#[derive(Debug)]
+pub struct MemoEvent {
+    pub version: u8,
+    pub memo: String,
+    pub is_lossy: bool,
+    pub signers: Vec<String>,
+}
+
+pub fn parse_instruction(instruction: &IndexedInstruction) -> Option<MemoEvent> {
+    let version = if instruction.program_id() == MEMO_V1_PROGRAM_ID {
+        1
+    } else if instruction.program_id() == MEMO_V2_PROGRAM_ID {
+        2
+    } else {
+        return None;
+    };
+    let data = instruction.data();
+    let is_lossy = std::str::from_utf8(data).is_err();
+    Some(MemoEvent {
+        version,
+        memo: String::from_utf8_lossy(data).into_owned(),
+        is_lossy,
+        signers: instruction.accounts().iter().map(|x| x.to_string()).collect(),
+    })
+}
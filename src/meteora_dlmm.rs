@@ -0,0 +1,394 @@
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::Pubkey;
+
+use crate::decoding::{decode_event, find_event, get_account, get_cpi_events, Reader};
+use crate::instruction::IndexedInstruction;
+
+/// LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo
+pub const METEORA_DLMM_PROGRAM_ID: Pubkey = Pubkey([4, 233, 225, 47, 188, 132, 232, 38, 201, 50, 204, 233, 226, 100, 12, 206, 21, 89, 12, 28, 98, 115, 176, 146, 87, 8, 186, 59, 133, 32, 176, 188]);
+
+const INITIALIZE_LB_PAIR_DISCRIMINATOR: [u8; 8] = [45, 154, 237, 210, 221, 15, 166, 92];
+const INITIALIZE_PERMISSION_LB_PAIR_DISCRIMINATOR: [u8; 8] = [108, 102, 213, 85, 251, 3, 53, 21];
+const INITIALIZE_CUSTOMIZABLE_PERMISSIONLESS_LB_PAIR_DISCRIMINATOR: [u8; 8] = [46, 39, 41, 135, 111, 183, 200, 64];
+const INITIALIZE_POSITION_DISCRIMINATOR: [u8; 8] = [219, 192, 234, 71, 190, 191, 102, 80];
+const INITIALIZE_POSITION_PDA_DISCRIMINATOR: [u8; 8] = [46, 82, 125, 146, 85, 141, 228, 153];
+const INITIALIZE_POSITION_BY_OPERATOR_DISCRIMINATOR: [u8; 8] = [251, 189, 190, 244, 117, 254, 35, 148];
+const ADD_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [181, 157, 89, 67, 143, 182, 52, 72];
+const ADD_LIQUIDITY_BY_WEIGHT_DISCRIMINATOR: [u8; 8] = [28, 140, 238, 99, 231, 162, 21, 149];
+const ADD_LIQUIDITY_BY_STRATEGY_DISCRIMINATOR: [u8; 8] = [7, 3, 150, 127, 148, 40, 61, 200];
+const ADD_LIQUIDITY_BY_STRATEGY2_DISCRIMINATOR: [u8; 8] = [3, 221, 149, 218, 111, 141, 118, 213];
+const ADD_LIQUIDITY_BY_STRATEGY_ONE_SIDE_DISCRIMINATOR: [u8; 8] = [41, 5, 238, 175, 100, 225, 6, 205];
+const ADD_LIQUIDITY_ONE_SIDE_DISCRIMINATOR: [u8; 8] = [94, 155, 103, 151, 70, 95, 220, 165];
+const REMOVE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [80, 85, 209, 72, 24, 206, 177, 108];
+const REMOVE_LIQUIDITY_BY_RANGE_DISCRIMINATOR: [u8; 8] = [26, 82, 102, 152, 240, 74, 105, 26];
+const REMOVE_LIQUIDITY_BY_RANGE2_DISCRIMINATOR: [u8; 8] = [204, 2, 195, 145, 53, 145, 145, 205];
+
+const LB_PAIR_CREATE_EVENT_DISCRIMINATOR: [u8; 8] = [185, 74, 252, 125, 27, 215, 188, 111];
+const POSITION_CREATE_EVENT_DISCRIMINATOR: [u8; 8] = [144, 142, 252, 84, 157, 53, 37, 121];
+const POSITION_CLOSE_EVENT_DISCRIMINATOR: [u8; 8] = [255, 196, 16, 107, 28, 202, 53, 128];
+const ADD_LIQUIDITY_EVENT_DISCRIMINATOR: [u8; 8] = [31, 94, 125, 90, 227, 52, 61, 186];
+const REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR: [u8; 8] = [116, 244, 97, 232, 103, 31, 152, 58];
+const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [81, 108, 227, 190, 205, 208, 10, 196];
+const CLAIM_FEE_EVENT_DISCRIMINATOR: [u8; 8] = [75, 122, 154, 48, 140, 74, 123, 163];
+const CLAIM_REWARD_EVENT_DISCRIMINATOR: [u8; 8] = [148, 116, 134, 204, 22, 171, 85, 95];
+
+/// Size of the `BinLiquidityDistribution` items of `add_liquidity`.
+const BIN_LIQUIDITY_DISTRIBUTION_SIZE: usize = 4 + 2 + 2;
+/// Size of the `BinLiquidityDistributionByWeight` and `BinLiquidityReduction` items.
+const BIN_WEIGHT_SIZE: usize = 4 + 2;
+
+#[derive(Debug)]
+pub enum MeteoraDlmmEvent {
+    LbPairCreate(LbPairCreateEvent),
+    PositionCreate(PositionCreateEvent),
+    PositionClose(PositionCloseEvent),
+    AddLiquidity(LiquidityEvent),
+    RemoveLiquidity(LiquidityEvent),
+    Swap(SwapEvent),
+    Claim(ClaimEvent),
+}
+
+#[derive(Debug)]
+pub struct LbPairCreateEvent {
+    pub lb_pair: String,
+    pub token_x: String,
+    pub token_y: String,
+    pub bin_step: u16,
+    pub active_id: Option<i32>,
+}
+
+#[derive(Debug)]
+pub struct PositionCreateEvent {
+    pub lb_pair: String,
+    pub position: String,
+    pub owner: String,
+    pub lower_bin_id: Option<i32>,
+    pub upper_bin_id: Option<i32>,
+}
+
+#[derive(Debug)]
+pub struct PositionCloseEvent {
+    pub position: String,
+    pub owner: String,
+}
+
+/// An addition or a removal of liquidity, over the bins the instruction targets when its arguments name them.
+///
+/// The mints are the ones of the pair, see `LbPairCreateEvent`.
+#[derive(Debug)]
+pub struct LiquidityEvent {
+    pub lb_pair: String,
+    pub sender: String,
+    pub position: String,
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub active_bin_id: i32,
+    pub min_bin_id: Option<i32>,
+    pub max_bin_id: Option<i32>,
+}
+
+#[derive(Debug)]
+pub struct SwapEvent {
+    pub lb_pair: String,
+    pub user: String,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub swap_for_y: bool,
+    pub start_bin_id: i32,
+    pub end_bin_id: i32,
+    pub fee: u64,
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+    pub fee_bps: u128,
+}
+
+/// A claim of fees, or of the reward `reward_index` into `amount_x`.
+#[derive(Debug)]
+pub struct ClaimEvent {
+    pub claim_type: &'static str,
+    pub lb_pair: String,
+    pub position: String,
+    pub owner: String,
+    pub reward_index: Option<u64>,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+
+/// Parses `instruction` from the events it emitted through `emit_cpi!`, completed by its accounts and arguments.
+pub fn parse_instruction(instruction: &IndexedInstruction) -> Result<Option<MeteoraDlmmEvent>, Error> {
+    let events = get_cpi_events(instruction, METEORA_DLMM_PROGRAM_ID);
+    if events.is_empty() {
+        return Ok(None);
+    }
+    let mut reader = Reader::new(instruction.data());
+    let discriminator = match reader.read_discriminator() {
+        Ok(discriminator) => discriminator,
+        Err(_) => return Ok(None),
+    };
+
+    if let Some(data) = find_event(&events, SWAP_EVENT_DISCRIMINATOR) {
+        // Every swap instruction takes the mints of the pair after its reserves and the user token accounts.
+        let token_x_mint = get_account(instruction, 6)?;
+        let token_y_mint = get_account(instruction, 7)?;
+        let event = decode_event(instruction, data, |event| read_swap_event(event, token_x_mint, token_y_mint));
+        return Ok(event.map(MeteoraDlmmEvent::Swap));
+    }
+
+    let liquidity_events = [(ADD_LIQUIDITY_EVENT_DISCRIMINATOR, true), (REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR, false)];
+    for (event_discriminator, is_add) in liquidity_events {
+        if let Some(data) = find_event(&events, event_discriminator) {
+            let bin_range = get_bin_range(discriminator, &mut reader)?;
+            let event = decode_event(instruction, data, |event| read_liquidity_event(event, bin_range));
+            return match is_add {
+                true => Ok(event.map(MeteoraDlmmEvent::AddLiquidity)),
+                false => Ok(event.map(MeteoraDlmmEvent::RemoveLiquidity)),
+            };
+        }
+    }
+
+    if let Some(data) = find_event(&events, CLAIM_FEE_EVENT_DISCRIMINATOR) {
+        let event = decode_event(instruction, data, |event| Ok(ClaimEvent {
+            claim_type: "claim_fee",
+            lb_pair: event.read_pubkey()?,
+            position: event.read_pubkey()?,
+            owner: event.read_pubkey()?,
+            reward_index: None,
+            amount_x: event.read_u64()?,
+            amount_y: event.read_u64()?,
+        }));
+        return Ok(event.map(MeteoraDlmmEvent::Claim));
+    }
+
+    if let Some(data) = find_event(&events, CLAIM_REWARD_EVENT_DISCRIMINATOR) {
+        let event = decode_event(instruction, data, |event| Ok(ClaimEvent {
+            claim_type: "claim_reward",
+            lb_pair: event.read_pubkey()?,
+            position: event.read_pubkey()?,
+            owner: event.read_pubkey()?,
+            reward_index: Some(event.read_u64()?),
+            amount_x: event.read_u64()?,
+            amount_y: 0,
+        }));
+        return Ok(event.map(MeteoraDlmmEvent::Claim));
+    }
+
+    if let Some(data) = find_event(&events, POSITION_CREATE_EVENT_DISCRIMINATOR) {
+        let (lower_bin_id, upper_bin_id) = match discriminator {
+            INITIALIZE_POSITION_DISCRIMINATOR | INITIALIZE_POSITION_PDA_DISCRIMINATOR | INITIALIZE_POSITION_BY_OPERATOR_DISCRIMINATOR => {
+                let lower_bin_id = reader.read_i32()?;
+                let width = reader.read_i32()?;
+                (Some(lower_bin_id), Some(lower_bin_id + width - 1))
+            },
+            _ => (None, None),
+        };
+        let event = decode_event(instruction, data, |event| Ok(PositionCreateEvent {
+            lb_pair: event.read_pubkey()?,
+            position: event.read_pubkey()?,
+            owner: event.read_pubkey()?,
+            lower_bin_id,
+            upper_bin_id,
+        }));
+        return Ok(event.map(MeteoraDlmmEvent::PositionCreate));
+    }
+
+    if let Some(data) = find_event(&events, POSITION_CLOSE_EVENT_DISCRIMINATOR) {
+        let event = decode_event(instruction, data, |event| Ok(PositionCloseEvent {
+            position: event.read_pubkey()?,
+            owner: event.read_pubkey()?,
+        }));
+        return Ok(event.map(MeteoraDlmmEvent::PositionClose));
+    }
+
+    if let Some(data) = find_event(&events, LB_PAIR_CREATE_EVENT_DISCRIMINATOR) {
+        // The arguments of every pair initialization start with the active bin.
+        let active_id = match discriminator {
+            INITIALIZE_LB_PAIR_DISCRIMINATOR | INITIALIZE_PERMISSION_LB_PAIR_DISCRIMINATOR | INITIALIZE_CUSTOMIZABLE_PERMISSIONLESS_LB_PAIR_DISCRIMINATOR => Some(reader.read_i32()?),
+            _ => None,
+        };
+        let event = decode_event(instruction, data, |event| Ok(LbPairCreateEvent {
+            lb_pair: event.read_pubkey()?,
+            bin_step: event.read_u16()?,
+            token_x: event.read_pubkey()?,
+            token_y: event.read_pubkey()?,
+            active_id,
+        }));
+        return Ok(event.map(MeteoraDlmmEvent::LbPairCreate));
+    }
+
+    Ok(None)
+}
+
+/// Reads a `Swap` event, whose input and output mints are the ones of the pair in the direction of the swap.
+fn read_swap_event(event: &mut Reader, token_x_mint: String, token_y_mint: String) -> Result<SwapEvent, Error> {
+    let lb_pair = event.read_pubkey()?;
+    let user = event.read_pubkey()?;
+    let start_bin_id = event.read_i32()?;
+    let end_bin_id = event.read_i32()?;
+    let amount_in = event.read_u64()?;
+    let amount_out = event.read_u64()?;
+    let swap_for_y = event.read_bool()?;
+    let (mint_in, mint_out) = if swap_for_y { (token_x_mint, token_y_mint) } else { (token_y_mint, token_x_mint) };
+    Ok(SwapEvent {
+        lb_pair,
+        user,
+        mint_in,
+        mint_out,
+        amount_in,
+        amount_out,
+        swap_for_y,
+        start_bin_id,
+        end_bin_id,
+        fee: event.read_u64()?,
+        protocol_fee: event.read_u64()?,
+        fee_bps: event.read_u128()?,
+        host_fee: event.read_u64()?,
+    })
+}
+
+/// Reads an `AddLiquidity` or a `RemoveLiquidity` event, over the bins of `bin_range` when the instruction names them.
+fn read_liquidity_event(event: &mut Reader, bin_range: Option<(i32, i32)>) -> Result<LiquidityEvent, Error> {
+    Ok(LiquidityEvent {
+        lb_pair: event.read_pubkey()?,
+        sender: event.read_pubkey()?,
+        position: event.read_pubkey()?,
+        amount_x: event.read_u64()?,
+        amount_y: event.read_u64()?,
+        active_bin_id: event.read_i32()?,
+        min_bin_id: bin_range.map(|(min_bin_id, _)| min_bin_id),
+        max_bin_id: bin_range.map(|(_, max_bin_id)| max_bin_id),
+    })
+}
+
+/// Lowest and highest bins targeted by a liquidity instruction, from its arguments.
+///
+/// `remove_all_liquidity` and the instructions not listed here don't name their bins.
+fn get_bin_range(discriminator: [u8; 8], reader: &mut Reader) -> Result<Option<(i32, i32)>, Error> {
+    match discriminator {
+        ADD_LIQUIDITY_DISCRIMINATOR => {
+            // Skips the amounts.
+            reader.read_bytes(8 + 8)?;
+            read_bin_ids(reader, BIN_LIQUIDITY_DISTRIBUTION_SIZE)
+        },
+        ADD_LIQUIDITY_BY_WEIGHT_DISCRIMINATOR => {
+            // Skips the amounts, the active bin and the slippage.
+            reader.read_bytes(8 + 8 + 4 + 4)?;
+            read_bin_ids(reader, BIN_WEIGHT_SIZE)
+        },
+        ADD_LIQUIDITY_ONE_SIDE_DISCRIMINATOR => {
+            reader.read_bytes(8 + 4 + 4)?;
+            read_bin_ids(reader, BIN_WEIGHT_SIZE)
+        },
+        ADD_LIQUIDITY_BY_STRATEGY_DISCRIMINATOR | ADD_LIQUIDITY_BY_STRATEGY2_DISCRIMINATOR => {
+            reader.read_bytes(8 + 8 + 4 + 4)?;
+            Ok(Some((reader.read_i32()?, reader.read_i32()?)))
+        },
+        ADD_LIQUIDITY_BY_STRATEGY_ONE_SIDE_DISCRIMINATOR => {
+            reader.read_bytes(8 + 4 + 4)?;
+            Ok(Some((reader.read_i32()?, reader.read_i32()?)))
+        },
+        REMOVE_LIQUIDITY_DISCRIMINATOR => read_bin_ids(reader, BIN_WEIGHT_SIZE),
+        REMOVE_LIQUIDITY_BY_RANGE_DISCRIMINATOR | REMOVE_LIQUIDITY_BY_RANGE2_DISCRIMINATOR => {
+            Ok(Some((reader.read_i32()?, reader.read_i32()?)))
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Reads a vector of items of `item_size` bytes which start with a bin id, and returns the lowest and highest ids.
+fn read_bin_ids(reader: &mut Reader, item_size: usize) -> Result<Option<(i32, i32)>, Error> {
+    let length = reader.read_u32()?;
+    let mut range: Option<(i32, i32)> = None;
+    for _ in 0..length {
+        let item = reader.read_bytes(item_size)?;
+        let bin_id = i32::from_le_bytes(item[..4].try_into()?);
+        range = Some(match range {
+            Some((min_bin_id, max_bin_id)) => (min_bin_id.min(bin_id), max_bin_id.max(bin_id)),
+            None => (bin_id, bin_id),
+        });
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN_X_MINT: &str = "So11111111111111111111111111111111111111112";
+    const TOKEN_Y_MINT: &str = "EPjFWdd5AufqSSqeM2qyyEPvUBNTZdJmSdKjhdMnX1Ph";
+
+    // Instructions and events encoded by hand after the layouts of the program's IDL.
+    fn swap_event_data() -> Vec<u8> {
+        let mut data = [1u8; 32].to_vec();
+        data.extend([2u8; 32]);
+        data.extend((-1_523i32).to_le_bytes());
+        data.extend((-1_525i32).to_le_bytes());
+        data.extend(1_000_000_000u64.to_le_bytes());
+        data.extend(142_977_620u64.to_le_bytes());
+        data.push(1);
+        data.extend(2_500_000u64.to_le_bytes());
+        data.extend(125_000u64.to_le_bytes());
+        data.extend(25_000_000u128.to_le_bytes());
+        data.extend(0u64.to_le_bytes());
+        data
+    }
+
+    fn add_liquidity_by_strategy_instruction_data() -> Vec<u8> {
+        let mut data = ADD_LIQUIDITY_BY_STRATEGY_DISCRIMINATOR.to_vec();
+        data.extend(5_000_000_000u64.to_le_bytes());
+        data.extend(700_000_000u64.to_le_bytes());
+        data.extend((-1_524i32).to_le_bytes());
+        data.extend(3i32.to_le_bytes());
+        data.extend((-1_559i32).to_le_bytes());
+        data.extend((-1_490i32).to_le_bytes());
+        data.push(6);
+        data.extend([0u8; 64]);
+        data
+    }
+
+    fn add_liquidity_event_data() -> Vec<u8> {
+        let mut data = [1u8; 32].to_vec();
+        data.extend([2u8; 32]);
+        data.extend([3u8; 32]);
+        data.extend(4_999_999_998u64.to_le_bytes());
+        data.extend(699_999_999u64.to_le_bytes());
+        data.extend((-1_524i32).to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_read_swap_event() {
+        let data = swap_event_data();
+        let mut reader = Reader::new(&data);
+        let event = read_swap_event(&mut reader, TOKEN_X_MINT.to_string(), TOKEN_Y_MINT.to_string()).unwrap();
+        assert!(reader.is_empty());
+        assert_eq!(event.lb_pair, bs58::encode([1u8; 32]).into_string());
+        assert_eq!(event.user, bs58::encode([2u8; 32]).into_string());
+        assert_eq!((event.mint_in.as_str(), event.mint_out.as_str()), (TOKEN_X_MINT, TOKEN_Y_MINT));
+        assert_eq!((event.start_bin_id, event.end_bin_id), (-1_523, -1_525));
+        assert_eq!((event.amount_in, event.amount_out), (1_000_000_000, 142_977_620));
+        assert_eq!((event.fee, event.protocol_fee, event.fee_bps, event.host_fee), (2_500_000, 125_000, 25_000_000, 0));
+    }
+
+    #[test]
+    fn test_read_add_liquidity_by_strategy() {
+        let data = add_liquidity_by_strategy_instruction_data();
+        let mut reader = Reader::new(&data);
+        let discriminator = reader.read_discriminator().unwrap();
+        let bin_range = get_bin_range(discriminator, &mut reader).unwrap();
+        assert_eq!(bin_range, Some((-1_559, -1_490)));
+
+        let data = add_liquidity_event_data();
+        let event = read_liquidity_event(&mut Reader::new(&data), bin_range).unwrap();
+        assert_eq!((event.amount_x, event.amount_y, event.active_bin_id), (4_999_999_998, 699_999_999, -1_524));
+        assert_eq!((event.min_bin_id, event.max_bin_id), (Some(-1_559), Some(-1_490)));
+    }
+
+    #[test]
+    fn test_read_truncated_swap_event() {
+        let data = swap_event_data();
+        assert!(read_swap_event(&mut Reader::new(&data[..data.len() - 1]), TOKEN_X_MINT.to_string(), TOKEN_Y_MINT.to_string()).is_err());
+    }
+}
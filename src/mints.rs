@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::ops::Neg;
+
+use substreams::pb::substreams::Clock;
+use substreams::scalar::BigInt;
+use substreams::store::{StoreAdd, StoreAddBigInt, StoreGet, StoreGetBigInt, StoreGetString, StoreNew, StoreSet, StoreSetString};
+use substreams_database_change::tables::Tables;
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+
+use crate::dex_trades::WSOL_MINT;
+use crate::pb::block_events::{block_event::Event, BlockEvents};
+
+pub const SOL_DECIMALS: u32 = 9;
+
+/// Keeps the state of every mint, one key per field: `{mint}:{field}`.
+///
+/// Fields are `decimals`, `mint_authority`, `freeze_authority`, `name`, `symbol`, `uri` and `creation_slot`.
+/// The supply is kept separately by `store_mint_supplies`.
+#[substreams::handlers::store]
+fn store_mints(clock: Clock, block_events: BlockEvents, store: StoreSetString) {
+    for (ordinal, block_event) in block_events.events.iter().enumerate() {
+        let ordinal = ordinal as u64;
+        match &block_event.event {
+            Some(Event::InitializeMint(initialize_mint)) => {
+                let mint = &initialize_mint.mint;
+                store.set(ordinal, format!("{mint}:decimals"), &initialize_mint.decimals.to_string());
+                store.set(ordinal, format!("{mint}:mint_authority"), &initialize_mint.mint_authority);
+                store.set(ordinal, format!("{mint}:freeze_authority"), initialize_mint.freeze_authority.as_ref().unwrap_or(&"null".to_string()));
+                store.set(ordinal, format!("{mint}:creation_slot"), &clock.number.to_string());
+            },
+            Some(Event::SetMintAuthority(set_authority)) => {
+                let mint = &set_authority.mint;
+                let new_authority = set_authority.new_authority.as_ref().unwrap_or(&"null".to_string()).clone();
+                match set_authority.authority_type.as_str() {
+                    "mint_tokens" => store.set(ordinal, format!("{mint}:mint_authority"), &new_authority),
+                    "freeze_account" => store.set(ordinal, format!("{mint}:freeze_authority"), &new_authority),
+                    _ => (),
+                }
+            },
+            Some(Event::PumpfunCreate(create)) => {
+                let mint = &create.mint;
+                store.set(ordinal, format!("{mint}:name"), &create.name);
+                store.set(ordinal, format!("{mint}:symbol"), &create.symbol);
+                store.set(ordinal, format!("{mint}:uri"), &create.uri);
+            },
+            Some(Event::MintMetadata(metadata)) => {
+                let mint = &metadata.mint;
+                store.set(ordinal, format!("{mint}:name"), &metadata.name);
+                store.set(ordinal, format!("{mint}:symbol"), &metadata.symbol);
+                store.set(ordinal, format!("{mint}:uri"), &metadata.uri);
+            },
+            _ => (),
+        }
+    }
+}
+
+/// Keeps the supply minted and burned since the first indexed block by every mint, keyed by the mint address.
+///
+/// It is only the supply of the mints initialized since the first indexed block, see `MintStores::get_supply`.
+#[substreams::handlers::store]
+fn store_mint_supplies(block_events: BlockEvents, store: StoreAddBigInt) {
+    for (ordinal, block_event) in block_events.events.iter().enumerate() {
+        match &block_event.event {
+            Some(Event::MintTo(mint_to)) => store.add(ordinal as u64, &mint_to.mint, BigInt::from(mint_to.amount)),
+            Some(Event::Burn(burn)) => store.add(ordinal as u64, &burn.mint, BigInt::from(burn.amount).neg()),
+            _ => (),
+        }
+    }
+}
+
+pub struct MintStores {
+    pub mints: StoreGetString,
+    pub supplies: StoreGetBigInt,
+}
+
+impl MintStores {
+    pub fn get_field(&self, mint: &str, field: &str) -> Option<String> {
+        self.mints.get_last(format!("{mint}:{field}"))
+    }
+
+    /// Supply of `mint`, when it was initialized since the first indexed block with a supply of zero.
+    pub fn get_supply(&self, mint: &str) -> Option<BigInt> {
+        self.get_field(mint, "creation_slot")?;
+        Some(self.supplies.get_last(mint).unwrap_or(BigInt::zero()))
+    }
+}
+
+/// Decimals of `mint`, falling back to those of native SOL for WSOL, which was initialized at genesis and is
+/// also the quote of swaps paid in native SOL, such as pumpfun ones.
+pub fn get_decimals(mints_store: &StoreGetString, mint: &str) -> Option<u32> {
+    if mint == WSOL_MINT {
+        return Some(SOL_DECIMALS);
+    }
+    mints_store.get_last(format!("{mint}:decimals")).map(|x| x.parse().unwrap())
+}
+
+/// Upserts the current state of `mint` into the `mints` table.
+///
+/// The stores are read in `get` mode, so the row reflects the state at the end of the block.
+pub fn insert_mint(mint: &str, slot: u64, stores: &MintStores, tables: &mut Tables) {
+    let row = tables.create_row("mints", mint.to_string())
+        .set("slot", slot);
+    if let Some(supply) = stores.get_supply(mint) {
+        row.set("supply", supply.to_string());
+    }
+    for field in ["mint_authority", "freeze_authority", "name", "symbol", "uri"] {
+        row.set(field, stores.get_field(mint, field).unwrap_or("".to_string()));
+    }
+    for field in ["decimals", "creation_slot"] {
+        if let Some(value) = stores.get_field(mint, field) {
+            row.set(field, value);
+        }
+    }
+}
+
+/// Resolves mint decimals for a transaction.
+///
+/// Decimals are taken from the transaction meta token balances, falling back to `store_mints`
+/// for mints whose accounts don't show up in the meta.
+pub struct MintDecimals<'a> {
+    decimals: HashMap<String, u32>,
+    mints_store: &'a StoreGetString,
+}
+
+impl<'a> MintDecimals<'a> {
+    pub fn new(transaction: &ConfirmedTransaction, mints_store: &'a StoreGetString) -> Self {
+        let meta = transaction.meta.as_ref().unwrap();
+        let decimals = meta.pre_token_balances.iter().chain(meta.post_token_balances.iter())
+            .filter_map(|x| x.ui_token_amount.as_ref().map(|ui_token_amount| (x.mint.clone(), ui_token_amount.decimals)))
+            .collect();
+        MintDecimals { decimals, mints_store }
+    }
+
+    pub fn get(&self, mint: &str) -> Option<u32> {
+        self.decimals.get(mint).cloned().or_else(|| get_decimals(self.mints_store, mint))
+    }
+
+    pub fn get_ui_amount(&self, mint: &str, amount: u64) -> Option<String> {
+        self.get(mint).map(|decimals| get_ui_amount(amount, decimals))
+    }
+}
+
+/// Formats a base units `amount` as a decimal string, e.g. `1500000` with 6 decimals is `1.500000`.
+pub fn get_ui_amount(amount: u64, decimals: u32) -> String {
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let digits = format!("{:0>width$}", amount, width = decimals + 1);
+    let (integer_part, fractional_part) = digits.split_at(digits.len() - decimals);
+    format!("{integer_part}.{fractional_part}")
+}
@@ -0,0 +1,184 @@
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::transaction::TransactionContext;
+
+use crate::decoding::{decode_event, find_events, get_account, get_log_events, Reader};
+use crate::instruction::IndexedInstruction;
+use crate::order_books::{get_side_name, CancelEvent, CreateMarketEvent, FillEvent, LotSizes, OrderBookEvent, OrderEvent, SettleFundsEvent};
+use crate::token_transfers::TokenTransfers;
+
+/// opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb
+pub const OPENBOOK_V2_PROGRAM_ID: Pubkey = Pubkey([11, 254, 191, 189, 251, 171, 250, 208, 180, 101, 113, 191, 149, 140, 30, 184, 36, 120, 123, 176, 153, 75, 177, 132, 107, 41, 120, 7, 30, 23, 153, 190]);
+
+const CREATE_MARKET_DISCRIMINATOR: [u8; 8] = [103, 226, 97, 235, 200, 188, 251, 254];
+const PLACE_ORDER_DISCRIMINATOR: [u8; 8] = [51, 194, 155, 175, 109, 130, 96, 106];
+const PLACE_TAKE_ORDER_DISCRIMINATOR: [u8; 8] = [3, 44, 71, 3, 26, 199, 203, 85];
+const CANCEL_ORDER_DISCRIMINATOR: [u8; 8] = [95, 129, 237, 240, 8, 49, 223, 132];
+const CANCEL_ORDER_BY_CLIENT_ORDER_ID_DISCRIMINATOR: [u8; 8] = [115, 178, 201, 8, 175, 183, 123, 119];
+const CANCEL_ALL_ORDERS_DISCRIMINATOR: [u8; 8] = [196, 83, 243, 171, 17, 100, 160, 143];
+const SETTLE_FUNDS_DISCRIMINATOR: [u8; 8] = [238, 64, 163, 96, 75, 171, 16, 33];
+
+const FILL_LOG_DISCRIMINATOR: [u8; 8] = [150, 23, 41, 148, 152, 162, 215, 64];
+
+pub fn parse_instruction(instruction: &IndexedInstruction, context: &TransactionContext) -> Result<Option<OrderBookEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let discriminator = match reader.read_discriminator() {
+        Ok(discriminator) => discriminator,
+        Err(_) => return Ok(None),
+    };
+    let event = match discriminator {
+        CREATE_MARKET_DISCRIMINATOR => {
+            // Skips the name and the oracle config, a confidence filter and an optional maximum staleness.
+            let name_length = reader.read_u32()? as usize;
+            reader.read_bytes(name_length)?;
+            reader.read_u32()?;
+            reader.read_option(|reader| reader.read_u32())?;
+            let quote_lot_size = reader.read_i64()? as u64;
+            let base_lot_size = reader.read_i64()? as u64;
+            OrderBookEvent::CreateMarket(CreateMarketEvent {
+                market: get_account(instruction, 0)?,
+                base_mint: get_account(instruction, 8)?,
+                quote_mint: get_account(instruction, 9)?,
+                lot_sizes: LotSizes::OpenbookV2 { base_lot_size, quote_lot_size },
+            })
+        },
+        PLACE_ORDER_DISCRIMINATOR | PLACE_TAKE_ORDER_DISCRIMINATOR => {
+            let side = get_side_name(reader.read_u8()?);
+            let price_in_ticks = reader.read_i64()?;
+            let size_in_lots = reader.read_i64()? as u64;
+            let _max_quote_lots_including_fees = reader.read_i64()?;
+            let (instruction_type, market, client_order_id) = match discriminator {
+                PLACE_ORDER_DISCRIMINATOR => ("place_order", get_account(instruction, 4)?, Some(reader.read_u64()? as u128)),
+                _ => ("place_take_order", get_account(instruction, 2)?, None),
+            };
+            let order_type = get_order_type_name(reader.read_u8()?);
+            OrderBookEvent::PlaceOrder(OrderEvent {
+                instruction_type,
+                market,
+                trader: get_account(instruction, 0)?,
+                side,
+                order_type,
+                // Market orders match at any price.
+                price_in_ticks: if order_type == "market" { None } else { Some(price_in_ticks) },
+                size_in_lots,
+                client_order_id,
+                fills: get_fills(instruction),
+            })
+        },
+        CANCEL_ORDER_DISCRIMINATOR | CANCEL_ORDER_BY_CLIENT_ORDER_ID_DISCRIMINATOR | CANCEL_ALL_ORDERS_DISCRIMINATOR => {
+            let mut event = CancelEvent {
+                instruction_type: "cancel_order",
+                market: get_account(instruction, 2)?,
+                trader: get_account(instruction, 0)?,
+                side: None,
+                order_id: None,
+                client_order_id: None,
+                orders_cancelled: None,
+                lots_removed: None,
+            };
+            match discriminator {
+                CANCEL_ORDER_DISCRIMINATOR => event.order_id = Some(reader.read_u128()?),
+                CANCEL_ORDER_BY_CLIENT_ORDER_ID_DISCRIMINATOR => {
+                    event.instruction_type = "cancel_order_by_client_order_id";
+                    event.client_order_id = Some(reader.read_u64()?);
+                },
+                _ => {
+                    event.instruction_type = "cancel_all_orders";
+                    event.side = reader.read_option(|reader| reader.read_u8())?.map(get_side_name);
+                },
+            }
+            OrderBookEvent::CancelOrder(event)
+        },
+        SETTLE_FUNDS_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            OrderBookEvent::SettleFunds(SettleFundsEvent {
+                owner: get_account(instruction, 0)?,
+                market: get_account(instruction, 3)?,
+                base_amount: transfers.amount_from(&get_account(instruction, 5)?),
+                quote_amount: transfers.amount_from(&get_account(instruction, 6)?),
+            })
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+fn get_order_type_name(order_type: u8) -> &'static str {
+    match order_type {
+        0 => "limit",
+        1 => "immediate_or_cancel",
+        2 => "post_only",
+        3 => "market",
+        4 => "post_only_slide",
+        5 => "fill_or_kill",
+        _ => "unknown",
+    }
+}
+
+/// The fills of the order placed by `instruction`, from its `FillLog` events.
+fn get_fills(instruction: &IndexedInstruction) -> Vec<FillEvent> {
+    let events = get_log_events(instruction);
+    find_events(&events, FILL_LOG_DISCRIMINATOR).into_iter()
+        .filter_map(|data| decode_event(instruction, data, read_fill_log))
+        .collect()
+}
+
+/// Reads a `FillLog` event.
+fn read_fill_log(event: &mut Reader) -> Result<FillEvent, Error> {
+    // Skips the market.
+    event.read_bytes(32)?;
+    let taker_side = get_side_name(event.read_u8()?);
+    // Skips the maker slot, the maker out flag, the timestamp and the sequence number.
+    event.read_bytes(1 + 1 + 8 + 8)?;
+    let maker = event.read_pubkey()?;
+    // Skips the maker client order id, fee and timestamp.
+    event.read_bytes(8 + 8 + 8)?;
+    let taker = event.read_pubkey()?;
+    // Skips the taker client order id and fee.
+    event.read_bytes(8 + 8)?;
+    Ok(FillEvent {
+        maker,
+        taker,
+        taker_side,
+        price_in_ticks: event.read_i64()?,
+        size_in_lots: event.read_i64()? as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A fill of a bid, encoded by hand after the layout of the program's `FillLog` event.
+    fn fill_log_data() -> Vec<u8> {
+        let mut data = [1u8; 32].to_vec();
+        data.extend([0, 3, 0]);
+        data.extend(1_718_000_000u64.to_le_bytes());
+        data.extend(5_812u64.to_le_bytes());
+        data.extend([2u8; 32]);
+        data.extend([0u8; 8 + 8 + 8]);
+        data.extend([3u8; 32]);
+        data.extend([0u8; 8 + 8]);
+        data.extend(14_249i64.to_le_bytes());
+        data.extend(1_000i64.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_read_fill_log() {
+        let data = fill_log_data();
+        let mut reader = Reader::new(&data);
+        let fill = read_fill_log(&mut reader).unwrap();
+        assert!(reader.is_empty());
+        assert_eq!(fill.maker, bs58::encode([2u8; 32]).into_string());
+        assert_eq!(fill.taker, bs58::encode([3u8; 32]).into_string());
+        assert_eq!((fill.taker_side, fill.price_in_ticks, fill.size_in_lots), ("bid", 14_249, 1_000));
+    }
+
+    #[test]
+    fn test_read_truncated_fill_log() {
+        let data = fill_log_data();
+        assert!(read_fill_log(&mut Reader::new(&data[..data.len() - 1])).is_err());
+    }
+}
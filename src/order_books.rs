@@ -0,0 +1,193 @@
+use anyhow::Error;
+
+use substreams::store::{StoreGet, StoreGetString, StoreNew, StoreSet, StoreSetString};
+
+use substreams_solana_utils::transaction::TransactionContext;
+
+use crate::instruction::IndexedInstruction;
+use crate::mints::MintDecimals;
+use crate::openbook_v2::{self, OPENBOOK_V2_PROGRAM_ID};
+use crate::pb::block_events::{block_event::Event, BlockEvents};
+use crate::phoenix::{self, PHOENIX_PROGRAM_ID};
+
+/// An instruction of an order book program, OpenBook v2 or Phoenix.
+#[derive(Debug)]
+pub enum OrderBookEvent {
+    CreateMarket(CreateMarketEvent),
+    PlaceOrder(OrderEvent),
+    CancelOrder(CancelEvent),
+    SettleFunds(SettleFundsEvent),
+}
+
+#[derive(Debug)]
+pub struct CreateMarketEvent {
+    pub market: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub lot_sizes: LotSizes,
+}
+
+/// The sizes which convert the ticks and the lots of a market into UI prices and sizes.
+#[derive(Debug)]
+pub enum LotSizes {
+    /// Native amounts per lot.
+    OpenbookV2 { base_lot_size: u64, quote_lot_size: u64 },
+    /// Lots per whole token, where a Phoenix base unit can be several whole tokens.
+    Phoenix {
+        tick_size_in_quote_lots_per_base_unit: u64,
+        num_base_lots_per_base_unit: u64,
+        num_quote_lots_per_quote_unit: u64,
+        raw_base_units_per_base_unit: u32,
+    },
+}
+
+/// An order placed by `trader`, with the fills it made as a taker.
+///
+/// The price is missing for market orders which don't set a limit.
+#[derive(Debug)]
+pub struct OrderEvent {
+    pub instruction_type: &'static str,
+    pub market: String,
+    pub trader: String,
+    pub side: &'static str,
+    pub order_type: &'static str,
+    pub price_in_ticks: Option<i64>,
+    pub size_in_lots: u64,
+    pub client_order_id: Option<u128>,
+    pub fills: Vec<FillEvent>,
+}
+
+#[derive(Debug)]
+pub struct FillEvent {
+    pub maker: String,
+    pub taker: String,
+    pub taker_side: &'static str,
+    pub price_in_ticks: i64,
+    pub size_in_lots: u64,
+}
+
+/// A cancellation of one order, by id or by client id, or of several orders.
+///
+/// The number of orders cancelled and the lots removed are known when the program logs them.
+#[derive(Debug)]
+pub struct CancelEvent {
+    pub instruction_type: &'static str,
+    pub market: String,
+    pub trader: String,
+    pub side: Option<&'static str>,
+    pub order_id: Option<u128>,
+    pub client_order_id: Option<u64>,
+    pub orders_cancelled: Option<u32>,
+    pub lots_removed: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct SettleFundsEvent {
+    pub market: String,
+    pub owner: String,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+/// Name of the side of an order, from its Borsh encoding.
+pub fn get_side_name(side: u8) -> &'static str {
+    match side {
+        0 => "bid",
+        _ => "ask",
+    }
+}
+
+/// Parses an instruction of any order book program.
+pub fn parse_instruction(instruction: &IndexedInstruction, context: &TransactionContext) -> Result<Option<OrderBookEvent>, Error> {
+    let program_id = instruction.program_id();
+    if program_id == OPENBOOK_V2_PROGRAM_ID {
+        openbook_v2::parse_instruction(instruction, context)
+    } else if program_id == PHOENIX_PROGRAM_ID {
+        phoenix::parse_instruction(instruction, context)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Keeps the mints and the lot sizes of every order book market, one key per field: `{market}:{field}`.
+///
+/// Fields are `program`, `base_mint`, `quote_mint`, and the fields of `LotSizes` of the program.
+#[substreams::handlers::store]
+fn store_order_book_markets(block_events: BlockEvents, store: StoreSetString) {
+    for (ordinal, block_event) in block_events.events.iter().enumerate() {
+        let market = match &block_event.event {
+            Some(Event::OrderBookMarket(market)) => market,
+            _ => continue,
+        };
+        let fields = [
+            ("program", Some(market.program.clone())),
+            ("base_mint", Some(market.base_mint.clone())),
+            ("quote_mint", Some(market.quote_mint.clone())),
+            ("base_lot_size", market.base_lot_size.map(|x| x.to_string())),
+            ("quote_lot_size", market.quote_lot_size.map(|x| x.to_string())),
+            ("tick_size_in_quote_lots_per_base_unit", market.tick_size_in_quote_lots_per_base_unit.map(|x| x.to_string())),
+            ("num_base_lots_per_base_unit", market.num_base_lots_per_base_unit.map(|x| x.to_string())),
+            ("num_quote_lots_per_quote_unit", market.num_quote_lots_per_quote_unit.map(|x| x.to_string())),
+            ("raw_base_units_per_base_unit", market.raw_base_units_per_base_unit.map(|x| x.to_string())),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                store.set(ordinal as u64, format!("{}:{field}", market.market), &value);
+            }
+        }
+    }
+}
+
+pub struct OrderBookMarkets {
+    pub store: StoreGetString,
+}
+
+impl OrderBookMarkets {
+    pub fn get_field(&self, market: &str, field: &str) -> Option<String> {
+        self.store.get_last(format!("{market}:{field}"))
+    }
+
+    fn get_lot_sizes(&self, market: &str) -> Option<LotSizes> {
+        let get_u64 = |field: &str| self.get_field(market, field)?.parse::<u64>().ok();
+        match self.get_field(market, "program")?.as_str() {
+            "openbook_v2" => Some(LotSizes::OpenbookV2 {
+                base_lot_size: get_u64("base_lot_size")?,
+                quote_lot_size: get_u64("quote_lot_size")?,
+            }),
+            "phoenix" => Some(LotSizes::Phoenix {
+                tick_size_in_quote_lots_per_base_unit: get_u64("tick_size_in_quote_lots_per_base_unit")?,
+                num_base_lots_per_base_unit: get_u64("num_base_lots_per_base_unit")?,
+                num_quote_lots_per_quote_unit: get_u64("num_quote_lots_per_quote_unit")?,
+                raw_base_units_per_base_unit: get_u64("raw_base_units_per_base_unit")? as u32,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Price of `price_in_ticks` in quote tokens per base token, when the market was created since the first indexed block.
+    pub fn get_price(&self, market: &str, price_in_ticks: i64, mint_decimals: &MintDecimals) -> Option<f64> {
+        match self.get_lot_sizes(market)? {
+            LotSizes::OpenbookV2 { base_lot_size, quote_lot_size } => {
+                let base_decimals = mint_decimals.get(&self.get_field(market, "base_mint")?)? as i32;
+                let quote_decimals = mint_decimals.get(&self.get_field(market, "quote_mint")?)? as i32;
+                Some(price_in_ticks as f64 * quote_lot_size as f64 / base_lot_size as f64 * 10f64.powi(base_decimals - quote_decimals))
+            },
+            LotSizes::Phoenix { tick_size_in_quote_lots_per_base_unit, num_quote_lots_per_quote_unit, raw_base_units_per_base_unit, .. } => {
+                Some(price_in_ticks as f64 * tick_size_in_quote_lots_per_base_unit as f64 / num_quote_lots_per_quote_unit as f64 / raw_base_units_per_base_unit as f64)
+            },
+        }
+    }
+
+    /// Size of `size_in_lots` in base tokens, when the market was created since the first indexed block.
+    pub fn get_size(&self, market: &str, size_in_lots: u64, mint_decimals: &MintDecimals) -> Option<f64> {
+        match self.get_lot_sizes(market)? {
+            LotSizes::OpenbookV2 { base_lot_size, .. } => {
+                let base_decimals = mint_decimals.get(&self.get_field(market, "base_mint")?)? as i32;
+                Some(size_in_lots as f64 * base_lot_size as f64 / 10f64.powi(base_decimals))
+            },
+            LotSizes::Phoenix { num_base_lots_per_base_unit, raw_base_units_per_base_unit, .. } => {
+                Some(size_in_lots as f64 / num_base_lots_per_base_unit as f64 * raw_base_units_per_base_unit as f64)
+            },
+        }
+    }
+}
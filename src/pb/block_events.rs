@@ -0,0 +1,211 @@
+// @generated
+/// The events the stores are built from, in execution order, so that the block is only parsed once for them.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockEvents {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<BlockEvent>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockEvent {
+    #[prost(uint32, tag="1")]
+    pub transaction_index: u32,
+    #[prost(int32, tag="2")]
+    pub instruction_index: i32,
+    #[prost(oneof="block_event::Event", tags="3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14")]
+    pub event: ::core::option::Option<block_event::Event>,
+}
+/// Nested message and enum types in `BlockEvent`.
+pub mod block_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag="3")]
+        InitializeMint(super::InitializeMint),
+        #[prost(message, tag="4")]
+        SetMintAuthority(super::SetMintAuthority),
+        #[prost(message, tag="5")]
+        MintMetadata(super::MintMetadata),
+        #[prost(message, tag="6")]
+        MintTo(super::MintTo),
+        #[prost(message, tag="7")]
+        Burn(super::Burn),
+        #[prost(message, tag="8")]
+        RaydiumAmmPool(super::RaydiumAmmPool),
+        #[prost(message, tag="9")]
+        PumpfunCreate(super::PumpfunCreate),
+        #[prost(message, tag="10")]
+        PumpfunSwap(super::PumpfunSwap),
+        #[prost(message, tag="11")]
+        PumpfunComplete(super::PumpfunComplete),
+        #[prost(message, tag="12")]
+        PumpfunSetParams(super::PumpfunSetParams),
+        #[prost(message, tag="13")]
+        PumpswapMigration(super::PumpswapMigration),
+        #[prost(message, tag="14")]
+        OrderBookMarket(super::OrderBookMarket),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitializeMint {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub decimals: u32,
+    #[prost(string, tag="3")]
+    pub mint_authority: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="4")]
+    pub freeze_authority: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// A change of the mint or freeze authority of a mint, which is revoked without a new authority.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMintAuthority {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub authority_type: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="3")]
+    pub new_authority: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// Metadata of a mint created with the Token Metadata program.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MintMetadata {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub uri: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MintTo {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub amount: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Burn {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub amount: u64,
+}
+/// An initialize, deposit, withdraw or swap of a Raydium AMM v4 pool, with the pool fields it reveals.
+///
+/// Swaps don't reveal the LP mint, and their mints are those of the vaults at the end of the transaction.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RaydiumAmmPool {
+    #[prost(string, tag="1")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub amm: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub coin_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub pc_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub lp_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub creator: ::prost::alloc::string::String,
+    #[prost(uint64, optional, tag="7")]
+    pub pool_coin_amount: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="8")]
+    pub pool_pc_amount: ::core::option::Option<u64>,
+    #[prost(string, tag="9")]
+    pub coin_vault: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub pc_vault: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PumpfunCreate {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub bonding_curve: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub uri: ::prost::alloc::string::String,
+}
+/// A pumpfun swap, where the transferred fee is the sum of the transfers to the fee recipient, which only buys make.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PumpfunSwap {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub sol_amount: u64,
+    #[prost(uint64, optional, tag="3")]
+    pub virtual_sol_reserves: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="4")]
+    pub virtual_token_reserves: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="5")]
+    pub real_sol_reserves: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="6")]
+    pub real_token_reserves: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="7")]
+    pub transferred_fee_lamports: ::core::option::Option<u64>,
+}
+/// A withdrawal of the liquidity of a complete bonding curve.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PumpfunComplete {
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PumpfunSetParams {
+    #[prost(uint64, tag="1")]
+    pub fee_basis_points: u64,
+}
+/// A PumpSwap pool created by pumpfun for a complete bonding curve.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PumpswapMigration {
+    #[prost(string, tag="1")]
+    pub base_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub pool: ::prost::alloc::string::String,
+}
+/// A market created on an order book program, with the lot sizes of the program.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderBookMarket {
+    #[prost(string, tag="1")]
+    pub market: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub program: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub base_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub quote_mint: ::prost::alloc::string::String,
+    #[prost(uint64, optional, tag="5")]
+    pub base_lot_size: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="6")]
+    pub quote_lot_size: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="7")]
+    pub tick_size_in_quote_lots_per_base_unit: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="8")]
+    pub num_base_lots_per_base_unit: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="9")]
+    pub num_quote_lots_per_quote_unit: ::core::option::Option<u64>,
+    #[prost(uint32, optional, tag="10")]
+    pub raw_base_units_per_base_unit: ::core::option::Option<u32>,
+}
+// @@protoc_insertion_point(module)
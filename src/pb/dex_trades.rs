@@ -0,0 +1,53 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DexTrades {
+    #[prost(message, repeated, tag="1")]
+    pub trades: ::prost::alloc::vec::Vec<DexTrade>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DexTrade {
+    #[prost(uint32, tag="1")]
+    pub transaction_index: u32,
+    #[prost(int32, tag="2")]
+    pub instruction_index: i32,
+    #[prost(string, tag="3")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub protocol: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub trader: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub signer: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub base_mint: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub quote_mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag="10")]
+    pub base_amount: u64,
+    #[prost(uint64, tag="11")]
+    pub quote_amount: u64,
+    #[prost(string, tag="12")]
+    pub side: ::prost::alloc::string::String,
+    #[prost(double, optional, tag="13")]
+    pub price: ::core::option::Option<f64>,
+    #[prost(int32, tag="14")]
+    pub parent_instruction_index: i32,
+    #[prost(int32, tag="15")]
+    pub top_instruction_index: i32,
+    #[prost(string, tag="16")]
+    pub parent_instruction_program_id: ::prost::alloc::string::String,
+    #[prost(string, tag="17")]
+    pub top_instruction_program_id: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag="18")]
+    pub base_decimals: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="19")]
+    pub quote_decimals: ::core::option::Option<u32>,
+    /// Position of the trade among the trades of its instruction, as multi-hop instructions make several.
+    #[prost(uint32, tag="20")]
+    pub trade_index: u32,
+}
+// @@protoc_insertion_point(module)
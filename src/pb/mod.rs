@@ -0,0 +1,11 @@
+// @generated
+// @@protoc_insertion_point(attribute:block_events)
+pub mod block_events {
+    include!("block_events.rs");
+    // @@protoc_insertion_point(block_events)
+}
+// @@protoc_insertion_point(attribute:dex_trades)
+pub mod dex_trades {
+    include!("dex_trades.rs");
+    // @@protoc_insertion_point(dex_trades)
+}
@@ -0,0 +1,334 @@
+use anyhow::Error;
+
+use substreams::log;
+
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::transaction::TransactionContext;
+
+use crate::decoding::{decode_event, get_account, Reader};
+use crate::instruction::IndexedInstruction;
+use crate::order_books::{get_side_name, CancelEvent, CreateMarketEvent, FillEvent, LotSizes, OrderBookEvent, OrderEvent, SettleFundsEvent};
+use crate::token_transfers::TokenTransfers;
+
+/// PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY
+pub const PHOENIX_PROGRAM_ID: Pubkey = Pubkey([5, 208, 234, 79, 51, 115, 112, 19, 165, 99, 224, 147, 72, 237, 182, 244, 89, 61, 145, 252, 118, 65, 249, 36, 124, 36, 65, 168, 66, 161, 187, 235]);
+
+const SWAP_TAG: u8 = 0;
+const SWAP_WITH_FREE_FUNDS_TAG: u8 = 1;
+const PLACE_LIMIT_ORDER_TAG: u8 = 2;
+const PLACE_LIMIT_ORDER_WITH_FREE_FUNDS_TAG: u8 = 3;
+const REDUCE_ORDER_TAG: u8 = 4;
+const REDUCE_ORDER_WITH_FREE_FUNDS_TAG: u8 = 5;
+const CANCEL_ALL_ORDERS_TAG: u8 = 6;
+const CANCEL_ALL_ORDERS_WITH_FREE_FUNDS_TAG: u8 = 7;
+const CANCEL_UP_TO_TAG: u8 = 8;
+const CANCEL_UP_TO_WITH_FREE_FUNDS_TAG: u8 = 9;
+const CANCEL_MULTIPLE_ORDERS_BY_ID_TAG: u8 = 10;
+const CANCEL_MULTIPLE_ORDERS_BY_ID_WITH_FREE_FUNDS_TAG: u8 = 11;
+const WITHDRAW_FUNDS_TAG: u8 = 12;
+const LOG_TAG: u8 = 15;
+const INITIALIZE_MARKET_TAG: u8 = 100;
+
+/// Size of the `AuditLogHeader` which starts every batch of logged events, its tag included.
+const AUDIT_LOG_HEADER_SIZE: usize = 1 + 1 + 8 + 8 + 8 + 32 + 32 + 2;
+
+/// The market events logged by Phoenix which are indexed.
+enum MarketEvent {
+    Fill { maker: String, order_sequence_number: u64, price_in_ticks: u64, base_lots_filled: u64 },
+    Reduce { base_lots_removed: u64 },
+    Other,
+}
+
+pub fn parse_instruction(instruction: &IndexedInstruction, context: &TransactionContext) -> Result<Option<OrderBookEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let tag = match reader.read_u8() {
+        Ok(tag) => tag,
+        Err(_) => return Ok(None),
+    };
+    let event = match tag {
+        INITIALIZE_MARKET_TAG => {
+            // Skips the sizes of the bids, the asks and the seats.
+            reader.read_bytes(8 + 8 + 8)?;
+            let num_quote_lots_per_quote_unit = reader.read_u64()?;
+            let tick_size_in_quote_lots_per_base_unit = reader.read_u64()?;
+            let num_base_lots_per_base_unit = reader.read_u64()?;
+            // Skips the taker fee and the fee collector.
+            reader.read_bytes(2 + 32)?;
+            let raw_base_units_per_base_unit = reader.read_option(|reader| reader.read_u32())?.unwrap_or(1);
+            OrderBookEvent::CreateMarket(CreateMarketEvent {
+                market: get_account(instruction, 2)?,
+                base_mint: get_account(instruction, 4)?,
+                quote_mint: get_account(instruction, 5)?,
+                lot_sizes: LotSizes::Phoenix {
+                    tick_size_in_quote_lots_per_base_unit,
+                    num_base_lots_per_base_unit,
+                    num_quote_lots_per_quote_unit,
+                    raw_base_units_per_base_unit,
+                },
+            })
+        },
+        SWAP_TAG | SWAP_WITH_FREE_FUNDS_TAG | PLACE_LIMIT_ORDER_TAG | PLACE_LIMIT_ORDER_WITH_FREE_FUNDS_TAG => {
+            let instruction_type = match tag {
+                SWAP_TAG => "swap",
+                SWAP_WITH_FREE_FUNDS_TAG => "swap_with_free_funds",
+                PLACE_LIMIT_ORDER_TAG => "place_limit_order",
+                _ => "place_limit_order_with_free_funds",
+            };
+            let (side, order_type, price_in_ticks, size_in_lots) = match read_order_packet(&mut reader)? {
+                Some(order) => order,
+                None => return Ok(None),
+            };
+            let client_order_id = reader.read_u128()?;
+            let trader = get_account(instruction, 3)?;
+            let fills = get_market_events(instruction).into_iter()
+                .filter_map(|event| match event {
+                    MarketEvent::Fill { maker, order_sequence_number, price_in_ticks, base_lots_filled } => Some(FillEvent {
+                        maker,
+                        taker: trader.clone(),
+                        taker_side: get_taker_side(order_sequence_number),
+                        price_in_ticks: price_in_ticks as i64,
+                        size_in_lots: base_lots_filled,
+                    }),
+                    _ => None,
+                })
+                .collect();
+            OrderBookEvent::PlaceOrder(OrderEvent {
+                instruction_type,
+                market: get_account(instruction, 2)?,
+                trader,
+                side,
+                order_type,
+                price_in_ticks: price_in_ticks.map(|x| x as i64),
+                size_in_lots,
+                client_order_id: Some(client_order_id),
+                fills,
+            })
+        },
+        REDUCE_ORDER_TAG | REDUCE_ORDER_WITH_FREE_FUNDS_TAG | CANCEL_ALL_ORDERS_TAG | CANCEL_ALL_ORDERS_WITH_FREE_FUNDS_TAG
+        | CANCEL_UP_TO_TAG | CANCEL_UP_TO_WITH_FREE_FUNDS_TAG | CANCEL_MULTIPLE_ORDERS_BY_ID_TAG | CANCEL_MULTIPLE_ORDERS_BY_ID_WITH_FREE_FUNDS_TAG => {
+            let instruction_type = match tag {
+                REDUCE_ORDER_TAG => "reduce_order",
+                REDUCE_ORDER_WITH_FREE_FUNDS_TAG => "reduce_order_with_free_funds",
+                CANCEL_ALL_ORDERS_TAG => "cancel_all_orders",
+                CANCEL_ALL_ORDERS_WITH_FREE_FUNDS_TAG => "cancel_all_orders_with_free_funds",
+                CANCEL_UP_TO_TAG => "cancel_up_to",
+                CANCEL_UP_TO_WITH_FREE_FUNDS_TAG => "cancel_up_to_with_free_funds",
+                CANCEL_MULTIPLE_ORDERS_BY_ID_TAG => "cancel_multiple_orders_by_id",
+                _ => "cancel_multiple_orders_by_id_with_free_funds",
+            };
+            // Reductions and cancellations of a single order start with its side.
+            let side = match tag {
+                REDUCE_ORDER_TAG | REDUCE_ORDER_WITH_FREE_FUNDS_TAG | CANCEL_UP_TO_TAG | CANCEL_UP_TO_WITH_FREE_FUNDS_TAG => Some(get_side_name(reader.read_u8()?)),
+                _ => None,
+            };
+            let reductions: Vec<u64> = get_market_events(instruction).into_iter()
+                .filter_map(|event| match event {
+                    MarketEvent::Reduce { base_lots_removed } => Some(base_lots_removed),
+                    _ => None,
+                })
+                .collect();
+            OrderBookEvent::CancelOrder(CancelEvent {
+                instruction_type,
+                market: get_account(instruction, 2)?,
+                trader: get_account(instruction, 3)?,
+                side,
+                order_id: None,
+                client_order_id: None,
+                orders_cancelled: Some(reductions.len() as u32),
+                lots_removed: Some(reductions.iter().sum()),
+            })
+        },
+        WITHDRAW_FUNDS_TAG => {
+            let transfers = TokenTransfers::new(instruction, context);
+            OrderBookEvent::SettleFunds(SettleFundsEvent {
+                market: get_account(instruction, 2)?,
+                owner: get_account(instruction, 3)?,
+                base_amount: transfers.amount_from(&get_account(instruction, 6)?),
+                quote_amount: transfers.amount_from(&get_account(instruction, 7)?),
+            })
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+/// Side of the taker of a fill, from the sequence number of the maker order.
+///
+/// Phoenix inverts the sequence numbers of bids, so theirs have the highest bit set.
+fn get_taker_side(maker_order_sequence_number: u64) -> &'static str {
+    match maker_order_sequence_number.leading_zeros() {
+        0 => "ask",
+        _ => "bid",
+    }
+}
+
+/// Reads the side, the type, the price and the size of the order packet of a swap or of a limit order.
+///
+/// The order packet is an enum whose variants order their fields differently, and unknown variants are skipped.
+fn read_order_packet(reader: &mut Reader) -> Result<Option<(&'static str, &'static str, Option<u64>, u64)>, Error> {
+    let packet_type = reader.read_u8()?;
+    let side = get_side_name(reader.read_u8()?);
+    let (order_type, price_in_ticks, size_in_lots) = match packet_type {
+        0 => ("post_only", Some(reader.read_u64()?), reader.read_u64()?),
+        1 => {
+            let price_in_ticks = reader.read_u64()?;
+            let size_in_lots = reader.read_u64()?;
+            // Skips the self trade behavior and the match limit.
+            reader.read_u8()?;
+            reader.read_option(|reader| reader.read_u64())?;
+            ("limit", Some(price_in_ticks), size_in_lots)
+        },
+        2 => {
+            let price_in_ticks = reader.read_option(|reader| reader.read_u64())?;
+            let size_in_lots = reader.read_u64()?;
+            // Skips the quote lots, the minimum lots to fill, the self trade behavior and the match limit.
+            reader.read_bytes(8 + 8 + 8 + 1)?;
+            reader.read_option(|reader| reader.read_u64())?;
+            ("immediate_or_cancel", price_in_ticks, size_in_lots)
+        },
+        _ => {
+            log::info!("Skipping unknown Phoenix order packet type {}.", packet_type);
+            return Ok(None);
+        },
+    };
+    Ok(Some((side, order_type, price_in_ticks, size_in_lots)))
+}
+
+/// The market events of `instruction`, from the `Log` instructions it invokes.
+fn get_market_events(instruction: &IndexedInstruction) -> Vec<MarketEvent> {
+    let mut events = Vec::new();
+    for inner_instruction in instruction.inner_instructions().iter() {
+        if inner_instruction.program_id() != PHOENIX_PROGRAM_ID || inner_instruction.data().first() != Some(&LOG_TAG) {
+            continue;
+        }
+        if let Some(log_events) = decode_event(instruction, &inner_instruction.data()[1..], read_market_events) {
+            events.extend(log_events);
+        }
+    }
+    events
+}
+
+/// Reads the market events of the data of a `Log` instruction, after its tag.
+fn read_market_events(reader: &mut Reader) -> Result<Vec<MarketEvent>, Error> {
+    let mut events = Vec::new();
+    reader.read_bytes(AUDIT_LOG_HEADER_SIZE)?;
+    while !reader.is_empty() {
+        let event = match reader.read_u8()? {
+            // Fill
+            2 => {
+                let _index = reader.read_u16()?;
+                let maker = reader.read_pubkey()?;
+                let order_sequence_number = reader.read_u64()?;
+                let price_in_ticks = reader.read_u64()?;
+                let base_lots_filled = reader.read_u64()?;
+                let _base_lots_remaining = reader.read_u64()?;
+                MarketEvent::Fill { maker, order_sequence_number, price_in_ticks, base_lots_filled }
+            },
+            // Place
+            3 => {
+                reader.read_bytes(2 + 8 + 16 + 8 + 8)?;
+                MarketEvent::Other
+            },
+            // Reduce
+            4 => {
+                reader.read_bytes(2 + 8 + 8)?;
+                let base_lots_removed = reader.read_u64()?;
+                let _base_lots_remaining = reader.read_u64()?;
+                MarketEvent::Reduce { base_lots_removed }
+            },
+            // Evict and ExpiredOrder
+            5 | 9 => {
+                reader.read_bytes(2 + 32 + 8 + 8 + 8)?;
+                MarketEvent::Other
+            },
+            // FillSummary
+            6 => {
+                reader.read_bytes(2 + 16 + 8 + 8 + 8)?;
+                MarketEvent::Other
+            },
+            // Fee
+            7 => {
+                reader.read_bytes(2 + 8)?;
+                MarketEvent::Other
+            },
+            // TimeInForce
+            8 => {
+                reader.read_bytes(2 + 8 + 8 + 8)?;
+                MarketEvent::Other
+            },
+            // The size of an unknown event isn't known, so the rest of the log can't be read.
+            tag => {
+                log::info!("Skipping the rest of the Phoenix log after unknown market event {}.", tag);
+                break;
+            },
+        };
+        events.push(event);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An immediate or cancel buy and the log of its fill, encoded by hand after the layouts of the program's instructions and events.
+    fn swap_instruction_data() -> Vec<u8> {
+        let mut data = vec![SWAP_TAG, 2, 0, 1];
+        data.extend(14_250u64.to_le_bytes());
+        data.extend(1_000u64.to_le_bytes());
+        data.extend([0u8; 8 + 8 + 8]);
+        data.push(1);
+        data.push(0);
+        data.extend(42u128.to_le_bytes());
+        data
+    }
+
+    fn log_instruction_data() -> Vec<u8> {
+        let mut data = vec![LOG_TAG];
+        data.extend([0u8; AUDIT_LOG_HEADER_SIZE]);
+        data.push(2);
+        data.extend(1u16.to_le_bytes());
+        data.extend([9u8; 32]);
+        data.extend(73_214u64.to_le_bytes());
+        data.extend(14_249u64.to_le_bytes());
+        data.extend(1_000u64.to_le_bytes());
+        data.extend(0u64.to_le_bytes());
+        data.push(6);
+        data.extend(2u16.to_le_bytes());
+        data.extend(42u128.to_le_bytes());
+        data.extend([0u8; 8 + 8 + 8]);
+        data
+    }
+
+    #[test]
+    fn test_read_order_packet() {
+        let data = swap_instruction_data();
+        let mut reader = Reader::new(&data);
+        assert_eq!(reader.read_u8().unwrap(), SWAP_TAG);
+        assert_eq!(read_order_packet(&mut reader).unwrap(), Some(("bid", "immediate_or_cancel", Some(14_250), 1_000)));
+        assert_eq!(reader.read_u128().unwrap(), 42);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_read_market_events() {
+        let data = log_instruction_data();
+        let events = read_market_events(&mut Reader::new(&data[1..])).unwrap();
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            MarketEvent::Fill { maker, order_sequence_number, price_in_ticks, base_lots_filled } => {
+                assert_eq!(maker, &bs58::encode([9u8; 32]).into_string());
+                assert_eq!((*order_sequence_number, *price_in_ticks, *base_lots_filled), (73_214, 14_249, 1_000));
+                assert_eq!(get_taker_side(*order_sequence_number), "bid");
+            },
+            _ => panic!("The first event isn't a fill."),
+        }
+        assert!(matches!(events[1], MarketEvent::Other));
+    }
+
+    #[test]
+    fn test_read_truncated_market_events() {
+        let data = log_instruction_data();
+        assert!(read_market_events(&mut Reader::new(&data[1..data.len() - 1])).is_err());
+    }
+}
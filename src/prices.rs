@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use substreams::store::{StoreGet, StoreGetFloat64, StoreNew, StoreSet, StoreSetFloat64};
+
+use crate::dex_trades::{get_base_ui_amount, get_quote_ui_amount, QUOTE_MINTS, WSOL_MINT, USDC_MINT, USDT_MINT};
+use crate::pb::block_events::{block_event::Event, BlockEvents};
+use crate::pb::dex_trades::DexTrades;
+
+const SOL_USD_KEY: &str = "sol_usd";
+
+/// Minimum USD value of the quote side of a trade for it to count towards the price of its base mint.
+const MIN_QUOTE_USD_VALUE: f64 = 10.0;
+
+/// Keeps the SOL/USD price under the `sol_usd` key.
+///
+/// The price is taken from the reserves of the Raydium AMM reference pools given as comma separated params.
+/// Reference pools must have SOL as coin mint and a USD stablecoin with 6 decimals as pc mint.
+#[substreams::handlers::store]
+fn store_sol_usd_price(params: String, block_events: BlockEvents, store: StoreSetFloat64) {
+    let reference_pools: Vec<&str> = params.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()).collect();
+
+    for (ordinal, block_event) in block_events.events.iter().enumerate() {
+        let pool = match &block_event.event {
+            Some(Event::RaydiumAmmPool(pool)) if pool.instruction_type == "swap" => pool,
+            _ => continue,
+        };
+        if !reference_pools.contains(&pool.amm.as_str()) {
+            continue;
+        }
+        let pool_coin_amount = pool.pool_coin_amount.unwrap_or(0) as f64 / 1e9;
+        let pool_pc_amount = pool.pool_pc_amount.unwrap_or(0) as f64 / 1e6;
+        if pool_coin_amount > 0.0 {
+            store.set(ordinal as u64, SOL_USD_KEY, &(pool_pc_amount / pool_coin_amount));
+        }
+    }
+}
+
+/// Keeps the USD price of every traded mint, keyed by the mint address.
+///
+/// Prices are the volume weighted average price of the block, over the trades quoted in one of `QUOTE_MINTS`
+/// worth at least `MIN_QUOTE_USD_VALUE`, so that dust trades against thin pools don't set the price.
+#[substreams::handlers::store]
+fn store_token_usd_prices(dex_trades: DexTrades, sol_usd_price_store: StoreGetFloat64, store: StoreSetFloat64) {
+    let sol_usd_price = sol_usd_price_store.get_last(SOL_USD_KEY);
+    // USD volume, base volume and ordinal of the last trade of every mint.
+    let mut volumes: HashMap<&str, (f64, f64, u64)> = HashMap::new();
+    for (ordinal, trade) in dex_trades.trades.iter().enumerate() {
+        if QUOTE_MINTS.contains(&trade.base_mint.as_str()) {
+            continue;
+        }
+        let quote_usd_price = match trade.quote_mint.as_str() {
+            USDC_MINT | USDT_MINT => Some(1.0),
+            WSOL_MINT => sol_usd_price,
+            _ => None,
+        };
+        let (quote_usd_price, quote_ui_amount, base_ui_amount) = match (quote_usd_price, get_quote_ui_amount(trade), get_base_ui_amount(trade)) {
+            (Some(quote_usd_price), Some(quote_ui_amount), Some(base_ui_amount)) => (quote_usd_price, quote_ui_amount, base_ui_amount),
+            _ => continue,
+        };
+        let usd_value = quote_ui_amount * quote_usd_price;
+        if usd_value < MIN_QUOTE_USD_VALUE || base_ui_amount <= 0.0 {
+            continue;
+        }
+        let volume = volumes.entry(&trade.base_mint).or_insert((0.0, 0.0, 0));
+        volume.0 += usd_value;
+        volume.1 += base_ui_amount;
+        volume.2 = ordinal as u64;
+    }
+    for (mint, (usd_volume, base_volume, ordinal)) in volumes {
+        store.set(ordinal, mint, &(usd_volume / base_volume));
+    }
+}
+
+pub struct UsdPrices {
+    pub sol_usd: StoreGetFloat64,
+    pub token_usd: StoreGetFloat64,
+}
+
+impl UsdPrices {
+    pub fn get_sol_usd_price(&self) -> Option<f64> {
+        self.sol_usd.get_last(SOL_USD_KEY)
+    }
+
+    pub fn get_usd_price(&self, mint: &str) -> Option<f64> {
+        match mint {
+            USDC_MINT | USDT_MINT => Some(1.0),
+            WSOL_MINT => self.get_sol_usd_price(),
+            _ => self.token_usd.get_last(mint),
+        }
+    }
+
+    /// USD value of `amount` base units of `mint`.
+    pub fn get_usd_value(&self, mint: &str, amount: u64, decimals: Option<u32>) -> Option<f64> {
+        Some(amount as f64 / 10f64.powi(decimals? as i32) * self.get_usd_price(mint)?)
+    }
+
+    /// USD value of `lamports`.
+    pub fn get_lamports_usd_value(&self, lamports: u64) -> Option<f64> {
+        Some(lamports as f64 / 1e9 * self.get_sol_usd_price()?)
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::{HashMap, HashSet};
+
+use substreams::pb::substreams::Clock;
+use substreams::scalar::BigInt;
+use substreams::store::{StoreAdd, StoreAddBigInt, StoreDelete, StoreGet, StoreGetBigInt, StoreGetString, StoreNew, StoreSet, StoreSetString};
+use substreams_database_change::tables::Tables;
+
+use substreams_solana_utils::transaction::TransactionContext;
+use substreams_solana_utils::system_program::constants::SYSTEM_PROGRAM_ID;
+
+use system_program_substream::pb::system_program::system_program_event;
+
+use crate::decoding::get_account;
+use crate::instruction::IndexedInstruction;
+use crate::pb::block_events::{block_event::Event, BlockEvents};
+use crate::pb::dex_trades::DexTrades;
+
+/// Fee of the pumpfun global account, until a SetParams is indexed.
+const DEFAULT_FEE_BASIS_POINTS: u64 = 100;
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Keeps the active pumpfun fee under the `fee_basis_points` key.
+#[substreams::handlers::store]
+fn store_pumpfun_params(block_events: BlockEvents, store: StoreSetString) {
+    for (ordinal, block_event) in block_events.events.iter().enumerate() {
+        if let Some(Event::PumpfunSetParams(set_params)) = &block_event.event {
+            store.set(ordinal as u64, "fee_basis_points", &set_params.fee_basis_points.to_string());
+        }
+    }
+}
+
+/// Keeps the pumpfun protocol revenue per day and per token, one key per field: `{day}:{mint}:{field}`.
+///
+/// Fields are `fee_lamports`, `sol_volume` and `swap_count`, and days are identified by their starting timestamp.
+/// A day is deleted once the next one starts, as its rows were written by the last block trading each token.
+///
+/// Fees are computed with the params active at the time of the swap, as both stores share the event ordinals.
+#[substreams::handlers::store]
+fn store_pumpfun_revenue(clock: Clock, block_events: BlockEvents, pumpfun_params_store: StoreGetString, store: StoreAddBigInt) {
+    let day = get_day(clock.timestamp.unwrap().seconds);
+    store.delete_prefix(0, &format!("{}:", day - SECONDS_PER_DAY));
+    for (ordinal, block_event) in block_events.events.iter().enumerate() {
+        if let Some(Event::PumpfunSwap(swap)) = &block_event.event {
+            let fee_basis_points = get_fee_basis_points_at(&pumpfun_params_store, ordinal as u64);
+            let fee_lamports = get_fee_lamports(swap.transferred_fee_lamports, swap.sol_amount, fee_basis_points);
+            let key = format!("{day}:{}", swap.mint);
+            store.add(ordinal as u64, format!("{key}:fee_lamports"), BigInt::from(fee_lamports));
+            store.add(ordinal as u64, format!("{key}:sol_volume"), BigInt::from(swap.sol_amount));
+            store.add(ordinal as u64, format!("{key}:swap_count"), BigInt::one());
+        }
+    }
+}
+
+/// Fee basis points active at the block event `ordinal`.
+fn get_fee_basis_points_at(params_store: &StoreGetString, ordinal: u64) -> Option<u64> {
+    params_store.get_at(ordinal, "fee_basis_points").map(|x| x.parse().unwrap())
+}
+
+fn get_day(timestamp: i64) -> i64 {
+    timestamp - timestamp.rem_euclid(SECONDS_PER_DAY)
+}
+
+/// Protocol fee paid by a pumpfun swap, in lamports.
+///
+/// Buys pay the fee with a system transfer to the fee recipient inside the swap instruction. Sells are paid
+/// out of the bonding curve without a transfer instruction, so the fee is computed from the fee basis points.
+pub fn get_fee_lamports(transferred_fee: Option<u64>, sol_amount: u64, fee_basis_points: Option<u64>) -> u64 {
+    let fee_basis_points = fee_basis_points.unwrap_or(DEFAULT_FEE_BASIS_POINTS);
+    transferred_fee.unwrap_or_else(|| (sol_amount as u128 * fee_basis_points as u128 / 10_000) as u64)
+}
+
+/// Sum of the system transfers made by a pumpfun swap to its fee recipient, which is its second account.
+pub fn get_transferred_fee(instruction: &IndexedInstruction, context: &TransactionContext) -> Option<u64> {
+    let fee_recipient = get_account(instruction, 1).ok()?;
+    let mut transferred_fee = None;
+    for inner_instruction in instruction.inner_instructions().iter() {
+        if inner_instruction.program_id() != SYSTEM_PROGRAM_ID {
+            continue;
+        }
+        if let Ok(Some(system_program_event::Event::Transfer(transfer))) = system_program_substream::parse_instruction(&inner_instruction.instruction, context) {
+            if transfer.recipient_account == fee_recipient {
+                transferred_fee = Some(transferred_fee.unwrap_or(0) + transfer.lamports);
+            }
+        }
+    }
+    transferred_fee
+}
+
+pub struct PumpfunFees {
+    pub params: StoreGetString,
+    pub revenue: StoreGetBigInt,
+    /// Block event ordinals of the pumpfun swaps of the block, by transaction and instruction index.
+    pub swap_ordinals: HashMap<(u32, i32), u64>,
+}
+
+impl PumpfunFees {
+    pub fn new(params: StoreGetString, revenue: StoreGetBigInt, block_events: &BlockEvents) -> Self {
+        let swap_ordinals = block_events.events.iter().enumerate()
+            .filter(|(_, block_event)| matches!(block_event.event, Some(Event::PumpfunSwap(_))))
+            .map(|(ordinal, block_event)| ((block_event.transaction_index, block_event.instruction_index), ordinal as u64))
+            .collect();
+        PumpfunFees { params, revenue, swap_ordinals }
+    }
+
+    /// Fee basis points active at the pumpfun swap at `instruction_index` of the transaction, so that the fee of
+    /// its row matches the one counted by `store_pumpfun_revenue`.
+    pub fn get_fee_basis_points(&self, transaction_index: u32, instruction_index: i32) -> Option<u64> {
+        let ordinal = self.swap_ordinals.get(&(transaction_index, instruction_index))?;
+        get_fee_basis_points_at(&self.params, *ordinal)
+    }
+}
+
+/// Upserts the daily revenue of every token traded on pumpfun in the block into the `pumpfun_daily_revenue` table.
+pub fn insert_pumpfun_revenue(dex_trades: &DexTrades, timestamp: i64, slot: u64, fees: &PumpfunFees, tables: &mut Tables) {
+    let day = get_day(timestamp);
+    let mints: HashSet<&String> = dex_trades.trades.iter().filter(|trade| trade.protocol == "pumpfun").map(|trade| &trade.base_mint).collect();
+    for mint in mints {
+        let key = format!("{day}:{mint}");
+        let get_field = |field: &str| fees.revenue.get_last(format!("{key}:{field}")).unwrap_or(BigInt::zero()).to_u64();
+        tables.create_row("pumpfun_daily_revenue", [("mint", mint.to_string()), ("timestamp", day.to_string())])
+            .set("fee_lamports", get_field("fee_lamports"))
+            .set("sol_volume", get_field("sol_volume"))
+            .set("swap_count", get_field("swap_count"))
+            .set("slot", slot);
+    }
+}
@@ -0,0 +1,99 @@
+use substreams::pb::substreams::Clock;
+use substreams::store::{StoreGet, StoreGetString, StoreNew, StoreSet, StoreSetString};
+use substreams_database_change::tables::Tables;
+
+use crate::pb::block_events::{block_event::Event, BlockEvents};
+use crate::raydium_pools::RaydiumPools;
+
+/// Real token reserves of a bonding curve at creation, in base units.
+const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+/// Total supply of a pumpfun token, in base units.
+const TOKEN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+
+/// Account which withdraws the liquidity of complete bonding curves and migrates it to a Raydium AMM pool.
+pub const PUMPFUN_MIGRATION_ACCOUNT: &str = "39azUYFWPz3VHgKCf3VChUwbpURdCHRxjWVowf5jUJjg";
+
+const RESERVE_FIELDS: [&str; 4] = ["virtual_sol_reserves", "virtual_token_reserves", "real_sol_reserves", "real_token_reserves"];
+
+/// Keeps the lifecycle of every pumpfun token, one key per field: `{mint}:{field}`.
+///
+/// Fields are `creator`, `bonding_curve` and `creation_slot`, set by Create, the latest reserves, set by
+/// Swap, `complete_slot`, set by Withdraw once the bonding curve is complete, and `pumpswap_pool`, set when
+/// the bonding curve migrates to PumpSwap.
+#[substreams::handlers::store]
+fn store_pumpfun_tokens(clock: Clock, block_events: BlockEvents, store: StoreSetString) {
+    let slot = clock.number.to_string();
+    for (ordinal, block_event) in block_events.events.iter().enumerate() {
+        let ordinal = ordinal as u64;
+        match &block_event.event {
+            Some(Event::PumpfunCreate(create)) => {
+                let mint = &create.mint;
+                store.set(ordinal, format!("{mint}:creator"), &create.user);
+                store.set(ordinal, format!("{mint}:bonding_curve"), &create.bonding_curve);
+                store.set(ordinal, format!("{mint}:creation_slot"), &slot);
+            },
+            Some(Event::PumpfunSwap(swap)) => {
+                let mint = &swap.mint;
+                let reserves = [swap.virtual_sol_reserves, swap.virtual_token_reserves, swap.real_sol_reserves, swap.real_token_reserves];
+                for (field, value) in RESERVE_FIELDS.iter().zip(reserves) {
+                    if let Some(value) = value {
+                        store.set(ordinal, format!("{mint}:{field}"), &value.to_string());
+                    }
+                }
+            },
+            Some(Event::PumpfunComplete(complete)) => {
+                store.set(ordinal, format!("{}:complete_slot", complete.mint), &slot);
+            },
+            Some(Event::PumpswapMigration(migration)) => {
+                store.set(ordinal, format!("{}:pumpswap_pool", migration.base_mint), &migration.pool);
+            },
+            _ => (),
+        }
+    }
+}
+
+pub struct PumpfunTokens {
+    pub store: StoreGetString,
+}
+
+impl PumpfunTokens {
+    pub fn get_field(&self, mint: &str, field: &str) -> Option<String> {
+        self.store.get_last(format!("{mint}:{field}"))
+    }
+
+    pub fn contains(&self, mint: &str) -> bool {
+        self.get_field(mint, "bonding_curve").is_some() || self.get_field(mint, "virtual_sol_reserves").is_some()
+    }
+}
+
+/// Upserts the lifecycle of the pumpfun token `mint` into the `pumpfun_tokens` table.
+///
+/// The bonding progress is the share of the initial real token reserves sold, and the market cap in SOL
+/// uses the bonding curve price for the whole supply. The Raydium AMM and the PumpSwap pool are the pools the
+/// bonding curve migrated to.
+pub fn insert_pumpfun_token(mint: &str, slot: u64, tokens: &PumpfunTokens, raydium_pools: &RaydiumPools, tables: &mut Tables) {
+    let row = tables.create_row("pumpfun_tokens", mint.to_string())
+        .set("creator", tokens.get_field(mint, "creator").unwrap_or_default())
+        .set("bonding_curve", tokens.get_field(mint, "bonding_curve").unwrap_or_default())
+        .set("raydium_amm", raydium_pools.get_field(mint, "first_amm").unwrap_or_default())
+        .set("pumpswap_pool", tokens.get_field(mint, "pumpswap_pool").unwrap_or_default())
+        .set("slot", slot);
+    for field in ["creation_slot", "complete_slot"] {
+        if let Some(value) = tokens.get_field(mint, field) {
+            row.set(field, value);
+        }
+    }
+
+    let reserves: Vec<Option<u64>> = RESERVE_FIELDS.iter().map(|field| tokens.get_field(mint, field).map(|x| x.parse().unwrap())).collect();
+    for (field, value) in RESERVE_FIELDS.iter().zip(reserves.iter()) {
+        row.set(field, value.unwrap_or(0));
+    }
+    if let [Some(virtual_sol_reserves), Some(virtual_token_reserves), _, Some(real_token_reserves)] = reserves[..] {
+        let bonding_progress = INITIAL_REAL_TOKEN_RESERVES.saturating_sub(real_token_reserves) as f64 / INITIAL_REAL_TOKEN_RESERVES as f64 * 100.0;
+        row.set("bonding_progress", bonding_progress.to_string());
+        if virtual_token_reserves > 0 {
+            let market_cap_sol = virtual_sol_reserves as f64 / virtual_token_reserves as f64 * TOKEN_TOTAL_SUPPLY as f64 / 1e9;
+            row.set("market_cap_sol", market_cap_sol.to_string());
+        }
+    }
+}
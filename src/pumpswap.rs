@@ -0,0 +1,284 @@
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::Pubkey;
+
+use crate::decoding::{decode_event, find_event, get_account, get_cpi_events, Reader};
+use crate::instruction::IndexedInstruction;
+
+/// pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA
+pub const PUMPSWAP_PROGRAM_ID: Pubkey = Pubkey([12, 20, 222, 252, 130, 94, 198, 118, 148, 37, 8, 24, 187, 101, 64, 101, 244, 41, 141, 49, 86, 213, 113, 180, 212, 248, 9, 12, 24, 233, 168, 99]);
+
+const CREATE_POOL_DISCRIMINATOR: [u8; 8] = [233, 146, 209, 142, 207, 104, 64, 188];
+const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+
+const CREATE_POOL_EVENT_DISCRIMINATOR: [u8; 8] = [177, 49, 12, 210, 160, 118, 167, 116];
+const BUY_EVENT_DISCRIMINATOR: [u8; 8] = [103, 244, 82, 31, 44, 245, 119, 119];
+const SELL_EVENT_DISCRIMINATOR: [u8; 8] = [62, 47, 55, 10, 165, 3, 220, 42];
+const DEPOSIT_EVENT_DISCRIMINATOR: [u8; 8] = [120, 248, 61, 83, 31, 142, 107, 144];
+const WITHDRAW_EVENT_DISCRIMINATOR: [u8; 8] = [22, 9, 133, 26, 160, 44, 71, 192];
+
+#[derive(Debug)]
+pub enum PumpswapEvent {
+    CreatePool(CreatePoolEvent),
+    Swap(SwapEvent),
+    Deposit(LiquidityEvent),
+    Withdraw(LiquidityEvent),
+}
+
+#[derive(Debug)]
+pub struct CreatePoolEvent {
+    pub pool: String,
+    pub creator: String,
+    pub index: u16,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub lp_mint: String,
+    pub base_amount_in: u64,
+    pub quote_amount_in: u64,
+    pub lp_token_amount_out: u64,
+}
+
+/// A buy or a sell of the base token of a pool.
+///
+/// The quote amount is what the user paid or received, fees included. The reserves are the pool reserves
+/// before the swap, as logged by the program, and the coin creator fee is missing from events logged
+/// before creator fees were introduced.
+#[derive(Debug)]
+pub struct SwapEvent {
+    pub pool: String,
+    pub user: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub direction: &'static str,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub coin_creator: Option<String>,
+    pub coin_creator_fee: Option<u64>,
+}
+
+/// A deposit or a withdrawal, with the pool reserves before it.
+#[derive(Debug)]
+pub struct LiquidityEvent {
+    pub pool: String,
+    pub user: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub lp_token_amount: u64,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub lp_mint_supply: u64,
+}
+
+pub fn parse_instruction(instruction: &IndexedInstruction) -> Result<Option<PumpswapEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let discriminator = match reader.read_discriminator() {
+        Ok(discriminator) => discriminator,
+        Err(_) => return Ok(None),
+    };
+    let events = get_cpi_events(instruction, PUMPSWAP_PROGRAM_ID);
+    let event = match discriminator {
+        CREATE_POOL_DISCRIMINATOR => {
+            let data = match find_event(&events, CREATE_POOL_EVENT_DISCRIMINATOR) {
+                Some(data) => data,
+                None => return Ok(None),
+            };
+            decode_event(instruction, data, read_create_pool_event).map(PumpswapEvent::CreatePool)
+        },
+        BUY_DISCRIMINATOR | SELL_DISCRIMINATOR => {
+            let (event_discriminator, direction) = match discriminator {
+                BUY_DISCRIMINATOR => (BUY_EVENT_DISCRIMINATOR, "buy"),
+                _ => (SELL_EVENT_DISCRIMINATOR, "sell"),
+            };
+            let data = match find_event(&events, event_discriminator) {
+                Some(data) => data,
+                None => return Ok(None),
+            };
+            let pool = get_account(instruction, 0)?;
+            let user = get_account(instruction, 1)?;
+            let base_mint = get_account(instruction, 3)?;
+            let quote_mint = get_account(instruction, 4)?;
+            decode_event(instruction, data, |event| read_swap_event(event, pool, user, base_mint, quote_mint, direction)).map(PumpswapEvent::Swap)
+        },
+        DEPOSIT_DISCRIMINATOR | WITHDRAW_DISCRIMINATOR => {
+            let event_discriminator = match discriminator {
+                DEPOSIT_DISCRIMINATOR => DEPOSIT_EVENT_DISCRIMINATOR,
+                _ => WITHDRAW_EVENT_DISCRIMINATOR,
+            };
+            let data = match find_event(&events, event_discriminator) {
+                Some(data) => data,
+                None => return Ok(None),
+            };
+            let pool = get_account(instruction, 0)?;
+            let user = get_account(instruction, 2)?;
+            let base_mint = get_account(instruction, 3)?;
+            let quote_mint = get_account(instruction, 4)?;
+            let liquidity_event = decode_event(instruction, data, |event| read_liquidity_event(event, pool, user, base_mint, quote_mint));
+            match discriminator {
+                DEPOSIT_DISCRIMINATOR => liquidity_event.map(PumpswapEvent::Deposit),
+                _ => liquidity_event.map(PumpswapEvent::Withdraw),
+            }
+        },
+        _ => return Ok(None),
+    };
+    Ok(event)
+}
+
+fn read_create_pool_event(event: &mut Reader) -> Result<CreatePoolEvent, Error> {
+    let _timestamp = event.read_i64()?;
+    let index = event.read_u16()?;
+    let creator = event.read_pubkey()?;
+    let base_mint = event.read_pubkey()?;
+    let quote_mint = event.read_pubkey()?;
+    // Skips the decimals of both mints.
+    event.read_bytes(1 + 1)?;
+    let base_amount_in = event.read_u64()?;
+    let quote_amount_in = event.read_u64()?;
+    // Skips the pool amounts, the minimum and initial liquidity.
+    event.read_bytes(8 + 8 + 8 + 8)?;
+    let lp_token_amount_out = event.read_u64()?;
+    let _pool_bump = event.read_u8()?;
+    Ok(CreatePoolEvent {
+        pool: event.read_pubkey()?,
+        lp_mint: event.read_pubkey()?,
+        creator,
+        index,
+        base_mint,
+        quote_mint,
+        base_amount_in,
+        quote_amount_in,
+        lp_token_amount_out,
+    })
+}
+
+/// Reads a `BuyEvent` or a `SellEvent`, which share their layout: the base amount and its limit, the reserves,
+/// the quote amount before fees, the fees, the quote amount with the LP fee and the quote amount of the user.
+fn read_swap_event(event: &mut Reader, pool: String, user: String, base_mint: String, quote_mint: String, direction: &'static str) -> Result<SwapEvent, Error> {
+    let _timestamp = event.read_i64()?;
+    let base_amount = event.read_u64()?;
+    let _limit_quote_amount = event.read_u64()?;
+    let _user_base_token_reserves = event.read_u64()?;
+    let _user_quote_token_reserves = event.read_u64()?;
+    let pool_base_token_reserves = event.read_u64()?;
+    let pool_quote_token_reserves = event.read_u64()?;
+    let _quote_amount = event.read_u64()?;
+    let _lp_fee_basis_points = event.read_u64()?;
+    let lp_fee = event.read_u64()?;
+    let _protocol_fee_basis_points = event.read_u64()?;
+    let protocol_fee = event.read_u64()?;
+    let _quote_amount_with_lp_fee = event.read_u64()?;
+    let quote_amount = event.read_u64()?;
+    // Skips the pool, the user and their token accounts, and the protocol fee recipient and its token account.
+    event.read_bytes(32 * 6)?;
+    let coin_creator = event.read_pubkey().ok();
+    let coin_creator_fee = match coin_creator {
+        Some(_) => {
+            let _coin_creator_fee_basis_points = event.read_u64()?;
+            Some(event.read_u64()?)
+        },
+        None => None,
+    };
+    Ok(SwapEvent {
+        pool,
+        user,
+        base_mint,
+        quote_mint,
+        direction,
+        base_amount,
+        quote_amount,
+        pool_base_token_reserves,
+        pool_quote_token_reserves,
+        lp_fee,
+        protocol_fee,
+        coin_creator,
+        coin_creator_fee,
+    })
+}
+
+/// Reads a `DepositEvent` or a `WithdrawEvent`, which share their layout: the LP token amount and the limits
+/// of the other amounts, the reserves, the amounts and the LP mint supply.
+fn read_liquidity_event(event: &mut Reader, pool: String, user: String, base_mint: String, quote_mint: String) -> Result<LiquidityEvent, Error> {
+    let _timestamp = event.read_i64()?;
+    let lp_token_amount = event.read_u64()?;
+    let _base_amount_limit = event.read_u64()?;
+    let _quote_amount_limit = event.read_u64()?;
+    let _user_base_token_reserves = event.read_u64()?;
+    let _user_quote_token_reserves = event.read_u64()?;
+    Ok(LiquidityEvent {
+        pool,
+        user,
+        base_mint,
+        quote_mint,
+        lp_token_amount,
+        pool_base_token_reserves: event.read_u64()?,
+        pool_quote_token_reserves: event.read_u64()?,
+        base_amount: event.read_u64()?,
+        quote_amount: event.read_u64()?,
+        lp_mint_supply: event.read_u64()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A buy of a pump.fun token with WSOL, encoded by hand after the layout of the program's `BuyEvent`.
+    fn buy_event_data(with_coin_creator: bool) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(1_750_000_000i64.to_le_bytes());
+        for amount in [
+            3_410_596_207u64, 100_500_000, 0, 0, 206_900_000_000_000, 84_994_117_647,
+            1_400_000, 20, 2_800, 5, 700, 1_402_800, 1_403_500,
+        ] {
+            data.extend(amount.to_le_bytes());
+        }
+        for account in 1..=6u8 {
+            data.extend([account; 32]);
+        }
+        if with_coin_creator {
+            data.extend([7u8; 32]);
+            data.extend(5u64.to_le_bytes());
+            data.extend(700u64.to_le_bytes());
+        }
+        data
+    }
+
+    fn read_buy_event(data: &[u8]) -> SwapEvent {
+        let mut reader = Reader::new(data);
+        let event = read_swap_event(&mut reader, "pool".to_string(), "user".to_string(), "base".to_string(), "quote".to_string(), "buy").unwrap();
+        assert!(reader.is_empty());
+        event
+    }
+
+    #[test]
+    fn test_read_swap_event() {
+        let event = read_buy_event(&buy_event_data(true));
+        assert_eq!((event.base_amount, event.quote_amount), (3_410_596_207, 1_403_500));
+        assert_eq!((event.pool_base_token_reserves, event.pool_quote_token_reserves), (206_900_000_000_000, 84_994_117_647));
+        assert_eq!((event.lp_fee, event.protocol_fee), (2_800, 700));
+        assert_eq!(event.coin_creator, Some(bs58::encode([7u8; 32]).into_string()));
+        assert_eq!(event.coin_creator_fee, Some(700));
+    }
+
+    #[test]
+    fn test_read_swap_event_without_coin_creator() {
+        let event = read_buy_event(&buy_event_data(false));
+        assert_eq!(event.quote_amount, 1_403_500);
+        assert_eq!((event.coin_creator, event.coin_creator_fee), (None, None));
+    }
+
+    #[test]
+    fn test_read_truncated_swap_event() {
+        let data = buy_event_data(false);
+        let result = read_swap_event(&mut Reader::new(&data[..data.len() - 1]), String::new(), String::new(), String::new(), String::new(), "buy");
+        assert!(result.is_err());
+    }
+}
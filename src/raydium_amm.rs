@@ -0,0 +1,224 @@
+use anyhow::{anyhow, Error};
+
+use substreams_database_change::tables::{Row, Tables};
+
+use crate::decoding::{get_account, Reader};
+use crate::instruction::IndexedInstruction;
+
+/// Raydium AMM v4 instructions which aren't decoded by `raydium_amm_substream`, or whose arguments it drops.
+#[allow(unused)]
+#[derive(Debug)]
+pub enum RaydiumAmmInstruction {
+    Initialize2 { nonce: u8, open_time: u64, init_pc_amount: u64, init_coin_amount: u64 },
+    MonitorStep { plan_order_limit: u16, place_order_limit: u16, cancel_order_limit: u16 },
+    MigrateToOpenBook,
+    SetParams { param: u8, value: SetParamsValue },
+    WithdrawSrm { amount: u64 },
+    SwapBaseIn { amount_in: u64, minimum_amount_out: u64 },
+    PreInitialize { nonce: u8 },
+    SwapBaseOut { max_amount_in: u64, amount_out: u64 },
+    SimulateInfo { param: u8 },
+    AdminCancelOrders { limit: u16 },
+    CreateConfigAccount,
+    UpdateConfigAccount { param: u8 },
+}
+
+/// Value of a SetParams instruction, whose layout depends on the param.
+#[derive(Debug)]
+pub enum SetParamsValue {
+    Value(Option<u64>),
+    AmmOwner(String),
+    Fees(Fees),
+    LastOrderDistance { numerator: u64, denominator: u64 },
+}
+
+/// Fees of a pool, as fractions.
+#[derive(Debug)]
+pub struct Fees {
+    pub min_separate_numerator: u64,
+    pub min_separate_denominator: u64,
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub pnl_numerator: u64,
+    pub pnl_denominator: u64,
+    pub swap_fee_numerator: u64,
+    pub swap_fee_denominator: u64,
+}
+
+impl RaydiumAmmInstruction {
+    pub fn unpack(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(data);
+        let instruction = match reader.read_u8()? {
+            1 => Self::Initialize2 {
+                nonce: reader.read_u8()?,
+                open_time: reader.read_u64()?,
+                init_pc_amount: reader.read_u64()?,
+                init_coin_amount: reader.read_u64()?,
+            },
+            2 => Self::MonitorStep {
+                plan_order_limit: reader.read_u16()?,
+                place_order_limit: reader.read_u16()?,
+                cancel_order_limit: reader.read_u16()?,
+            },
+            5 => Self::MigrateToOpenBook,
+            6 => {
+                let param = reader.read_u8()?;
+                let value = match param {
+                    FEES_PARAM => SetParamsValue::Fees(Fees {
+                        min_separate_numerator: reader.read_u64()?,
+                        min_separate_denominator: reader.read_u64()?,
+                        trade_fee_numerator: reader.read_u64()?,
+                        trade_fee_denominator: reader.read_u64()?,
+                        pnl_numerator: reader.read_u64()?,
+                        pnl_denominator: reader.read_u64()?,
+                        swap_fee_numerator: reader.read_u64()?,
+                        swap_fee_denominator: reader.read_u64()?,
+                    }),
+                    AMM_OWNER_PARAM => SetParamsValue::AmmOwner(reader.read_pubkey()?),
+                    LAST_ORDER_DISTANCE_PARAM => SetParamsValue::LastOrderDistance {
+                        numerator: reader.read_u64()?,
+                        denominator: reader.read_u64()?,
+                    },
+                    // Params which don't need a value, like `clear_open_time`, may be sent without one.
+                    _ => SetParamsValue::Value(reader.read_u64().ok()),
+                };
+                Self::SetParams { param, value }
+            },
+            8 => Self::WithdrawSrm { amount: reader.read_u64()? },
+            9 => Self::SwapBaseIn { amount_in: reader.read_u64()?, minimum_amount_out: reader.read_u64()? },
+            10 => Self::PreInitialize { nonce: reader.read_u8()? },
+            11 => Self::SwapBaseOut { max_amount_in: reader.read_u64()?, amount_out: reader.read_u64()? },
+            12 => Self::SimulateInfo { param: reader.read_u8()? },
+            13 => Self::AdminCancelOrders { limit: reader.read_u16()? },
+            14 => Self::CreateConfigAccount,
+            15 => Self::UpdateConfigAccount { param: reader.read_u8()? },
+            tag => return Err(anyhow!("Unknown Raydium AMM instruction {}.", tag)),
+        };
+        Ok(instruction)
+    }
+}
+
+const FEES_PARAM: u8 = 9;
+const AMM_OWNER_PARAM: u8 = 10;
+const LAST_ORDER_DISTANCE_PARAM: u8 = 12;
+
+/// Name of a SetParams `param`, as in the `AmmParams` enum of the program.
+fn get_param_name(param: u8) -> &'static str {
+    match param {
+        0 => "status",
+        1 => "state",
+        2 => "order_num",
+        3 => "depth",
+        4 => "amount_wave",
+        5 => "min_price_multiplier",
+        6 => "max_price_multiplier",
+        7 => "min_size",
+        8 => "vol_max_cut_ratio",
+        9 => "fees",
+        10 => "amm_owner",
+        11 => "set_open_time",
+        12 => "last_order_distance",
+        13 => "init_order_depth",
+        14 => "set_switch_time",
+        15 => "clear_open_time",
+        16 => "seperate",
+        17 => "update_open_order",
+        _ => "unknown",
+    }
+}
+
+/// Sets the swap type and the user's slippage limit on a `raydium_amm_swap_events` row.
+pub fn set_swap_limit_columns(instruction: &IndexedInstruction, row: &mut Row) {
+    match RaydiumAmmInstruction::unpack(instruction.data()) {
+        Ok(RaydiumAmmInstruction::SwapBaseIn { minimum_amount_out, .. }) => {
+            row
+                .set("swap_type", "swap_base_in")
+                .set("minimum_amount_out", minimum_amount_out);
+        },
+        Ok(RaydiumAmmInstruction::SwapBaseOut { max_amount_in, .. }) => {
+            row
+                .set("swap_type", "swap_base_out")
+                .set("max_amount_in", max_amount_in);
+        },
+        _ => (),
+    }
+}
+
+/// The pool open time of an Initialize2 instruction.
+pub fn get_open_time(instruction: &IndexedInstruction) -> Option<u64> {
+    match RaydiumAmmInstruction::unpack(instruction.data()) {
+        Ok(RaydiumAmmInstruction::Initialize2 { open_time, .. }) => Some(open_time),
+        _ => None,
+    }
+}
+
+/// The order book program and market an Initialize2 instruction pairs the pool with.
+pub fn get_market(instruction: &IndexedInstruction) -> Option<(String, String)> {
+    match RaydiumAmmInstruction::unpack(instruction.data()) {
+        Ok(RaydiumAmmInstruction::Initialize2 { .. }) => Some((get_account(instruction, 15).ok()?, get_account(instruction, 16).ok()?)),
+        _ => None,
+    }
+}
+
+/// Inserts the instructions which don't have a table of their own into the `raydium_amm_other_events` table.
+///
+/// `amm` is empty for instructions which don't take a pool account.
+pub fn insert_raydium_amm_other_event<'a>(instruction: &IndexedInstruction, tables: &'a mut Tables, slot: u64, transaction_index: u32) -> Option<&'a mut Row> {
+    let account = |index: usize| get_account(instruction, index).unwrap_or_default();
+    let (instruction_type, amm, param, value) = match RaydiumAmmInstruction::unpack(instruction.data()).ok()? {
+        RaydiumAmmInstruction::MonitorStep { .. } => ("monitor_step", account(3), None, None),
+        RaydiumAmmInstruction::MigrateToOpenBook => ("migrate_to_open_book", account(3), None, None),
+        RaydiumAmmInstruction::SetParams { param, value } => {
+            let row = tables.create_row("raydium_amm_other_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+                .set("type", "set_params")
+                .set("amm", account(1))
+                .set("param", get_param_name(param));
+            set_params_value_columns(value, row);
+            return Some(row);
+        },
+        RaydiumAmmInstruction::WithdrawSrm { amount } => ("withdraw_srm", account(1), None, Some(amount)),
+        RaydiumAmmInstruction::PreInitialize { .. } => ("pre_initialize", String::new(), None, None),
+        RaydiumAmmInstruction::SimulateInfo { .. } => ("simulate_info", account(0), None, None),
+        RaydiumAmmInstruction::AdminCancelOrders { limit } => ("admin_cancel_orders", account(1), None, Some(limit as u64)),
+        RaydiumAmmInstruction::CreateConfigAccount => ("create_config_account", String::new(), None, None),
+        RaydiumAmmInstruction::UpdateConfigAccount { .. } => ("update_config_account", String::new(), None, None),
+        _ => return None,
+    };
+    let row = tables.create_row("raydium_amm_other_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", instruction.index.to_string())])
+        .set("type", instruction_type)
+        .set("amm", amm)
+        .set("param", param.unwrap_or_default());
+    if let Some(value) = value {
+        row.set("value", value);
+    }
+    Some(row)
+}
+
+/// Sets the value of a SetParams instruction on a `raydium_amm_other_events` row.
+fn set_params_value_columns(value: SetParamsValue, row: &mut Row) {
+    match value {
+        SetParamsValue::Value(Some(value)) => {
+            row.set("value", value);
+        },
+        SetParamsValue::Value(None) => (),
+        SetParamsValue::AmmOwner(amm_owner) => {
+            row.set("new_amm_owner", amm_owner);
+        },
+        SetParamsValue::Fees(fees) => {
+            row
+                .set("min_separate_numerator", fees.min_separate_numerator)
+                .set("min_separate_denominator", fees.min_separate_denominator)
+                .set("trade_fee_numerator", fees.trade_fee_numerator)
+                .set("trade_fee_denominator", fees.trade_fee_denominator)
+                .set("pnl_numerator", fees.pnl_numerator)
+                .set("pnl_denominator", fees.pnl_denominator)
+                .set("swap_fee_numerator", fees.swap_fee_numerator)
+                .set("swap_fee_denominator", fees.swap_fee_denominator);
+        },
+        SetParamsValue::LastOrderDistance { numerator, denominator } => {
+            row
+                .set("last_order_numerator", numerator)
+                .set("last_order_denominator", denominator);
+        },
+    }
+}
@@ -0,0 +1,344 @@
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::transaction::TransactionContext;
+
+use crate::decoding::{decode_event, find_event, get_account, get_log_events, Reader};
+use crate::instruction::IndexedInstruction;
+use crate::token_transfers::TokenTransfers;
+
+/// CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey = Pubkey([165, 213, 202, 158, 4, 207, 93, 181, 144, 183, 20, 186, 47, 227, 44, 177, 89, 19, 63, 193, 193, 146, 183, 34, 87, 253, 7, 211, 156, 176, 64, 30]);
+
+const CREATE_POOL_DISCRIMINATOR: [u8; 8] = [233, 146, 209, 142, 207, 104, 64, 188];
+const OPEN_POSITION_DISCRIMINATOR: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+const OPEN_POSITION_V2_DISCRIMINATOR: [u8; 8] = [77, 184, 74, 214, 112, 86, 241, 199];
+const OPEN_POSITION_WITH_TOKEN22_NFT_DISCRIMINATOR: [u8; 8] = [77, 255, 174, 82, 125, 29, 201, 46];
+const CLOSE_POSITION_DISCRIMINATOR: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+const INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+const INCREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] = [133, 29, 89, 223, 69, 238, 176, 10];
+const DECREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
+const DECREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] = [58, 127, 188, 62, 79, 82, 196, 96];
+const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const SWAP_V2_DISCRIMINATOR: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+const COLLECT_PROTOCOL_FEE_DISCRIMINATOR: [u8; 8] = [136, 136, 252, 221, 194, 66, 126, 89];
+const COLLECT_FUND_FEE_DISCRIMINATOR: [u8; 8] = [167, 138, 78, 149, 223, 194, 6, 126];
+const COLLECT_REMAINING_REWARDS_DISCRIMINATOR: [u8; 8] = [18, 237, 166, 197, 34, 16, 213, 144];
+
+const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [64, 198, 205, 232, 38, 8, 113, 226];
+
+#[derive(Debug)]
+pub enum RaydiumClmmEvent {
+    CreatePool(CreatePoolEvent),
+    OpenPosition(OpenPositionEvent),
+    ClosePosition(ClosePositionEvent),
+    IncreaseLiquidity(LiquidityEvent),
+    DecreaseLiquidity(LiquidityEvent),
+    Swap(SwapEvent),
+    Collect(CollectEvent),
+}
+
+#[derive(Debug)]
+pub struct CreatePoolEvent {
+    pub pool_state: String,
+    pub creator: String,
+    pub amm_config: String,
+    pub token_mint_0: String,
+    pub token_mint_1: String,
+    pub token_vault_0: String,
+    pub token_vault_1: String,
+    pub sqrt_price_x64: u128,
+    pub open_time: u64,
+}
+
+#[derive(Debug)]
+pub struct OpenPositionEvent {
+    pub pool_state: String,
+    pub owner: String,
+    pub position_nft_mint: String,
+    pub personal_position: String,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub amount_0_max: u64,
+    pub amount_1_max: u64,
+    pub token_mint_0: Option<String>,
+    pub token_mint_1: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ClosePositionEvent {
+    pub owner: String,
+    pub position_nft_mint: String,
+    pub personal_position: String,
+}
+
+/// An increase or a decrease of the liquidity of a position.
+///
+/// The amount limits are maximums for increases, and minimums for decreases.
+#[derive(Debug)]
+pub struct LiquidityEvent {
+    pub pool_state: String,
+    pub owner: String,
+    pub personal_position: String,
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub amount_0_limit: u64,
+    pub amount_1_limit: u64,
+    pub token_mint_0: Option<String>,
+    pub token_mint_1: Option<String>,
+}
+
+/// A swap, with the pool state after it when the program logged its `SwapEvent`.
+#[derive(Debug)]
+pub struct SwapEvent {
+    pub pool_state: String,
+    pub user: String,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub is_base_input: bool,
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit_x64: u128,
+    pub sqrt_price_x64: Option<u128>,
+    pub liquidity: Option<u128>,
+    pub tick: Option<i32>,
+}
+
+/// A collection of fees or rewards, by a position owner or by the protocol.
+#[derive(Debug)]
+pub struct CollectEvent {
+    pub collect_type: &'static str,
+    pub pool_state: String,
+    pub user: String,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub token_mint_0: Option<String>,
+    pub token_mint_1: Option<String>,
+}
+
+pub fn parse_instruction(instruction: &IndexedInstruction, context: &TransactionContext) -> Result<Option<RaydiumClmmEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let discriminator = match reader.read_discriminator() {
+        Ok(discriminator) => discriminator,
+        Err(_) => return Ok(None),
+    };
+    let event = match discriminator {
+        CREATE_POOL_DISCRIMINATOR => RaydiumClmmEvent::CreatePool(CreatePoolEvent {
+            creator: get_account(instruction, 0)?,
+            amm_config: get_account(instruction, 1)?,
+            pool_state: get_account(instruction, 2)?,
+            token_mint_0: get_account(instruction, 3)?,
+            token_mint_1: get_account(instruction, 4)?,
+            token_vault_0: get_account(instruction, 5)?,
+            token_vault_1: get_account(instruction, 6)?,
+            sqrt_price_x64: reader.read_u128()?,
+            open_time: reader.read_u64()?,
+        }),
+        OPEN_POSITION_DISCRIMINATOR | OPEN_POSITION_V2_DISCRIMINATOR | OPEN_POSITION_WITH_TOKEN22_NFT_DISCRIMINATOR => {
+            // The Token-2022 NFT variant has no metadata account.
+            let offset = if discriminator == OPEN_POSITION_WITH_TOKEN22_NFT_DISCRIMINATOR { 1 } else { 0 };
+            let transfers = TokenTransfers::new(instruction, context);
+            let token_vault_0 = get_account(instruction, 12 - offset)?;
+            let token_vault_1 = get_account(instruction, 13 - offset)?;
+            let tick_lower_index = reader.read_i32()?;
+            let tick_upper_index = reader.read_i32()?;
+            let _tick_array_lower_start_index = reader.read_i32()?;
+            let _tick_array_upper_start_index = reader.read_i32()?;
+            RaydiumClmmEvent::OpenPosition(OpenPositionEvent {
+                owner: get_account(instruction, 1)?,
+                position_nft_mint: get_account(instruction, 2)?,
+                pool_state: get_account(instruction, 5 - offset)?,
+                personal_position: get_account(instruction, 9 - offset)?,
+                tick_lower_index,
+                tick_upper_index,
+                liquidity: reader.read_u128()?,
+                amount_0_max: reader.read_u64()?,
+                amount_1_max: reader.read_u64()?,
+                amount_0: transfers.amount_to(&token_vault_0),
+                amount_1: transfers.amount_to(&token_vault_1),
+                token_mint_0: transfers.get_mint(&token_vault_0),
+                token_mint_1: transfers.get_mint(&token_vault_1),
+            })
+        },
+        CLOSE_POSITION_DISCRIMINATOR => RaydiumClmmEvent::ClosePosition(ClosePositionEvent {
+            owner: get_account(instruction, 0)?,
+            position_nft_mint: get_account(instruction, 1)?,
+            personal_position: get_account(instruction, 3)?,
+        }),
+        INCREASE_LIQUIDITY_DISCRIMINATOR | INCREASE_LIQUIDITY_V2_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let token_vault_0 = get_account(instruction, 9)?;
+            let token_vault_1 = get_account(instruction, 10)?;
+            RaydiumClmmEvent::IncreaseLiquidity(LiquidityEvent {
+                owner: get_account(instruction, 0)?,
+                pool_state: get_account(instruction, 2)?,
+                personal_position: get_account(instruction, 4)?,
+                liquidity: reader.read_u128()?,
+                amount_0_limit: reader.read_u64()?,
+                amount_1_limit: reader.read_u64()?,
+                amount_0: transfers.amount_to(&token_vault_0),
+                amount_1: transfers.amount_to(&token_vault_1),
+                token_mint_0: transfers.get_mint(&token_vault_0),
+                token_mint_1: transfers.get_mint(&token_vault_1),
+            })
+        },
+        DECREASE_LIQUIDITY_DISCRIMINATOR | DECREASE_LIQUIDITY_V2_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let token_vault_0 = get_account(instruction, 5)?;
+            let token_vault_1 = get_account(instruction, 6)?;
+            let event = LiquidityEvent {
+                owner: get_account(instruction, 0)?,
+                personal_position: get_account(instruction, 2)?,
+                pool_state: get_account(instruction, 3)?,
+                liquidity: reader.read_u128()?,
+                amount_0_limit: reader.read_u64()?,
+                amount_1_limit: reader.read_u64()?,
+                amount_0: transfers.amount_from(&token_vault_0),
+                amount_1: transfers.amount_from(&token_vault_1),
+                token_mint_0: transfers.get_mint(&token_vault_0),
+                token_mint_1: transfers.get_mint(&token_vault_1),
+            };
+            // Position owners collect their fees by decreasing the liquidity by zero.
+            match event.liquidity {
+                0 => RaydiumClmmEvent::Collect(CollectEvent {
+                    collect_type: "collect_fees",
+                    pool_state: event.pool_state,
+                    user: event.owner,
+                    amount_0: event.amount_0,
+                    amount_1: event.amount_1,
+                    token_mint_0: event.token_mint_0,
+                    token_mint_1: event.token_mint_1,
+                }),
+                _ => RaydiumClmmEvent::DecreaseLiquidity(event),
+            }
+        },
+        SWAP_DISCRIMINATOR | SWAP_V2_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let input_vault = get_account(instruction, 5)?;
+            let output_vault = get_account(instruction, 6)?;
+            let (other_amount_threshold, sqrt_price_limit_x64, is_base_input) = read_swap_args(&mut reader)?;
+            let pool_state_after = find_event(&get_log_events(instruction), SWAP_EVENT_DISCRIMINATOR).and_then(|data| decode_event(instruction, data, read_swap_event));
+            let (sqrt_price_x64, liquidity, tick) = match pool_state_after {
+                Some((sqrt_price_x64, liquidity, tick)) => (Some(sqrt_price_x64), Some(liquidity), Some(tick)),
+                None => (None, None, None),
+            };
+            RaydiumClmmEvent::Swap(SwapEvent {
+                user: get_account(instruction, 0)?,
+                pool_state: get_account(instruction, 2)?,
+                mint_in: transfers.get_mint(&input_vault).unwrap_or_default(),
+                mint_out: transfers.get_mint(&output_vault).unwrap_or_default(),
+                amount_in: transfers.amount_to(&input_vault),
+                amount_out: transfers.amount_from(&output_vault),
+                is_base_input,
+                other_amount_threshold,
+                sqrt_price_limit_x64,
+                sqrt_price_x64,
+                liquidity,
+                tick,
+            })
+        },
+        COLLECT_PROTOCOL_FEE_DISCRIMINATOR | COLLECT_FUND_FEE_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let token_vault_0 = get_account(instruction, 3)?;
+            let token_vault_1 = get_account(instruction, 4)?;
+            RaydiumClmmEvent::Collect(CollectEvent {
+                collect_type: if discriminator == COLLECT_PROTOCOL_FEE_DISCRIMINATOR { "collect_protocol_fee" } else { "collect_fund_fee" },
+                user: get_account(instruction, 0)?,
+                pool_state: get_account(instruction, 1)?,
+                amount_0: transfers.amount_from(&token_vault_0),
+                amount_1: transfers.amount_from(&token_vault_1),
+                token_mint_0: transfers.get_mint(&token_vault_0),
+                token_mint_1: transfers.get_mint(&token_vault_1),
+            })
+        },
+        COLLECT_REMAINING_REWARDS_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let reward_token_vault = get_account(instruction, 3)?;
+            RaydiumClmmEvent::Collect(CollectEvent {
+                collect_type: "collect_remaining_rewards",
+                user: get_account(instruction, 0)?,
+                pool_state: get_account(instruction, 2)?,
+                amount_0: transfers.amount_from(&reward_token_vault),
+                amount_1: 0,
+                token_mint_0: transfers.get_mint(&reward_token_vault),
+                token_mint_1: None,
+            })
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+/// Reads the amount threshold, the sqrt price limit and the direction of the amount from the arguments of `swap` and `swap_v2`.
+fn read_swap_args(reader: &mut Reader) -> Result<(u64, u128, bool), Error> {
+    let _amount = reader.read_u64()?;
+    let other_amount_threshold = reader.read_u64()?;
+    let sqrt_price_limit_x64 = reader.read_u128()?;
+    let is_base_input = reader.read_bool()?;
+    Ok((other_amount_threshold, sqrt_price_limit_x64, is_base_input))
+}
+
+/// Reads the sqrt price, the liquidity and the tick of the pool after a swap from its `SwapEvent`.
+fn read_swap_event(reader: &mut Reader) -> Result<(u128, u128, i32), Error> {
+    // Skips the pool state, sender, token accounts, amounts, transfer fees and direction.
+    reader.read_bytes(32 * 4 + 8 * 4 + 1)?;
+    Ok((reader.read_u128()?, reader.read_u128()?, reader.read_i32()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A swap of 1 SOL for USDC, encoded by hand after the layouts of the program's IDL.
+    fn swap_instruction_data() -> Vec<u8> {
+        let mut data = SWAP_DISCRIMINATOR.to_vec();
+        data.extend(1_000_000_000u64.to_le_bytes());
+        data.extend(142_318_005u64.to_le_bytes());
+        data.extend(4_295_048_017u128.to_le_bytes());
+        data.push(1);
+        data
+    }
+
+    fn swap_event_data() -> Vec<u8> {
+        let mut data = Vec::new();
+        for account in 1..=4u8 {
+            data.extend([account; 32]);
+        }
+        for amount in [1_000_000_000u64, 0, 143_029_151, 0] {
+            data.extend(amount.to_le_bytes());
+        }
+        data.push(1);
+        data.extend(6_972_814_302_479_137_926u128.to_le_bytes());
+        data.extend(3_405_827_188_294u128.to_le_bytes());
+        data.extend((-19_452i32).to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_read_swap_args() {
+        let data = swap_instruction_data();
+        let mut reader = Reader::new(&data);
+        assert_eq!(reader.read_discriminator().unwrap(), SWAP_DISCRIMINATOR);
+        assert_eq!(read_swap_args(&mut reader).unwrap(), (142_318_005, 4_295_048_017, true));
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_read_swap_event() {
+        let data = swap_event_data();
+        let mut reader = Reader::new(&data);
+        assert_eq!(read_swap_event(&mut reader).unwrap(), (6_972_814_302_479_137_926, 3_405_827_188_294, -19_452));
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_read_truncated_swap_event() {
+        let data = swap_event_data();
+        assert!(read_swap_event(&mut Reader::new(&data[..data.len() - 1])).is_err());
+    }
+}
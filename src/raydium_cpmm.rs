@@ -0,0 +1,157 @@
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::transaction::TransactionContext;
+
+use crate::balances::PostTokenBalances;
+use crate::decoding::{get_account, Reader};
+use crate::instruction::IndexedInstruction;
+use crate::token_transfers::TokenTransfers;
+
+/// CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP5C
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = Pubkey([169, 42, 90, 139, 79, 41, 89, 82, 132, 37, 80, 170, 147, 253, 91, 149, 181, 172, 230, 168, 235, 146, 12, 147, 148, 46, 67, 105, 12, 32, 237, 91]);
+
+const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+const SWAP_BASE_INPUT_DISCRIMINATOR: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+const SWAP_BASE_OUTPUT_DISCRIMINATOR: [u8; 8] = [55, 217, 98, 86, 163, 74, 180, 173];
+
+#[derive(Debug)]
+pub enum RaydiumCpmmEvent {
+    Initialize(InitializeEvent),
+    Deposit(LiquidityEvent),
+    Withdraw(LiquidityEvent),
+    Swap(SwapEvent),
+}
+
+/// Vault balances are the balances at the end of the transaction.
+#[derive(Debug)]
+pub struct InitializeEvent {
+    pub pool_state: String,
+    pub creator: String,
+    pub amm_config: String,
+    pub token_0_mint: String,
+    pub token_1_mint: String,
+    pub lp_mint: String,
+    pub init_amount_0: u64,
+    pub init_amount_1: u64,
+    pub open_time: u64,
+    pub token_0_vault_balance: Option<u64>,
+    pub token_1_vault_balance: Option<u64>,
+}
+
+/// A deposit or a withdrawal.
+///
+/// The amount limits are maximums for deposits, and minimums for withdrawals.
+#[derive(Debug)]
+pub struct LiquidityEvent {
+    pub pool_state: String,
+    pub owner: String,
+    pub token_0_mint: String,
+    pub token_1_mint: String,
+    pub lp_mint: String,
+    pub lp_token_amount: u64,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub amount_0_limit: u64,
+    pub amount_1_limit: u64,
+    pub token_0_vault_balance: Option<u64>,
+    pub token_1_vault_balance: Option<u64>,
+}
+
+/// A swap, where the limit is the minimum amount out for base input swaps, and the maximum amount in for
+/// base output swaps.
+#[derive(Debug)]
+pub struct SwapEvent {
+    pub pool_state: String,
+    pub user: String,
+    pub swap_type: &'static str,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub limit_amount: u64,
+    pub input_vault_balance: Option<u64>,
+    pub output_vault_balance: Option<u64>,
+}
+
+pub fn parse_instruction(instruction: &IndexedInstruction, context: &TransactionContext, post_token_balances: &PostTokenBalances) -> Result<Option<RaydiumCpmmEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let discriminator = match reader.read_discriminator() {
+        Ok(discriminator) => discriminator,
+        Err(_) => return Ok(None),
+    };
+    let event = match discriminator {
+        INITIALIZE_DISCRIMINATOR => {
+            let token_0_vault = get_account(instruction, 10)?;
+            let token_1_vault = get_account(instruction, 11)?;
+            RaydiumCpmmEvent::Initialize(InitializeEvent {
+                creator: get_account(instruction, 0)?,
+                amm_config: get_account(instruction, 1)?,
+                pool_state: get_account(instruction, 3)?,
+                token_0_mint: get_account(instruction, 4)?,
+                token_1_mint: get_account(instruction, 5)?,
+                lp_mint: get_account(instruction, 6)?,
+                init_amount_0: reader.read_u64()?,
+                init_amount_1: reader.read_u64()?,
+                open_time: reader.read_u64()?,
+                token_0_vault_balance: post_token_balances.get(&token_0_vault),
+                token_1_vault_balance: post_token_balances.get(&token_1_vault),
+            })
+        },
+        DEPOSIT_DISCRIMINATOR | WITHDRAW_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let token_0_vault = get_account(instruction, 6)?;
+            let token_1_vault = get_account(instruction, 7)?;
+            let (amount_0, amount_1) = match discriminator {
+                DEPOSIT_DISCRIMINATOR => (transfers.amount_to(&token_0_vault), transfers.amount_to(&token_1_vault)),
+                _ => (transfers.amount_from(&token_0_vault), transfers.amount_from(&token_1_vault)),
+            };
+            let event = LiquidityEvent {
+                owner: get_account(instruction, 0)?,
+                pool_state: get_account(instruction, 2)?,
+                token_0_mint: get_account(instruction, 10)?,
+                token_1_mint: get_account(instruction, 11)?,
+                lp_mint: get_account(instruction, 12)?,
+                lp_token_amount: reader.read_u64()?,
+                amount_0_limit: reader.read_u64()?,
+                amount_1_limit: reader.read_u64()?,
+                amount_0,
+                amount_1,
+                token_0_vault_balance: post_token_balances.get(&token_0_vault),
+                token_1_vault_balance: post_token_balances.get(&token_1_vault),
+            };
+            match discriminator {
+                DEPOSIT_DISCRIMINATOR => RaydiumCpmmEvent::Deposit(event),
+                _ => RaydiumCpmmEvent::Withdraw(event),
+            }
+        },
+        SWAP_BASE_INPUT_DISCRIMINATOR | SWAP_BASE_OUTPUT_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let input_vault = get_account(instruction, 6)?;
+            let output_vault = get_account(instruction, 7)?;
+            let (swap_type, limit_amount) = match discriminator {
+                SWAP_BASE_INPUT_DISCRIMINATOR => {
+                    let _amount_in = reader.read_u64()?;
+                    ("swap_base_input", reader.read_u64()?)
+                },
+                _ => ("swap_base_output", reader.read_u64()?),
+            };
+            RaydiumCpmmEvent::Swap(SwapEvent {
+                user: get_account(instruction, 0)?,
+                pool_state: get_account(instruction, 3)?,
+                mint_in: get_account(instruction, 10)?,
+                mint_out: get_account(instruction, 11)?,
+                swap_type,
+                amount_in: transfers.amount_to(&input_vault),
+                amount_out: transfers.amount_from(&output_vault),
+                limit_amount,
+                input_vault_balance: post_token_balances.get(&input_vault),
+                output_vault_balance: post_token_balances.get(&output_vault),
+            })
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
@@ -0,0 +1,164 @@
+use substreams::pb::substreams::Clock;
+use substreams::scalar::BigInt;
+use substreams::store::{StoreGet, StoreGetBigInt, StoreGetString, StoreNew, StoreSet, StoreSetBigInt, StoreSetIfNotExists, StoreSetIfNotExistsString};
+use substreams_database_change::tables::{Row, Tables};
+
+use crate::mints::{get_decimals, MintStores};
+use crate::pb::block_events::{block_event::Event, BlockEvents};
+use crate::prices::UsdPrices;
+use crate::pumpfun_tokens::PUMPFUN_MIGRATION_ACCOUNT;
+
+/// Keeps the registry of Raydium AMM pools, one key per field: `{amm}:{field}`.
+///
+/// Fields are `coin_mint`, `pc_mint`, `lp_mint`, `coin_vault`, `pc_vault`, `creator` and `open_slot`, set by
+/// Initialize, and `first_seen_slot`. For pools initialized before the first indexed block, the mints and
+/// vaults are backfilled from Deposit and Withdraw, and from Swap except for the LP mint.
+///
+/// The pool a complete pumpfun token migrated to is also kept under `{mint}:first_amm`, for pools initialized by
+/// the pumpfun migration account after the bonding curve of the token was complete.
+#[substreams::handlers::store]
+fn store_raydium_pools(clock: Clock, block_events: BlockEvents, pumpfun_tokens_store: StoreGetString, store: StoreSetIfNotExistsString) {
+    let slot = clock.number.to_string();
+    for (ordinal, block_event) in block_events.events.iter().enumerate() {
+        let ordinal = ordinal as u64;
+        let pool = match &block_event.event {
+            Some(Event::RaydiumAmmPool(pool)) => pool,
+            _ => continue,
+        };
+        let amm = &pool.amm;
+        let fields = [
+            ("coin_mint", &pool.coin_mint),
+            ("pc_mint", &pool.pc_mint),
+            ("lp_mint", &pool.lp_mint),
+            ("coin_vault", &pool.coin_vault),
+            ("pc_vault", &pool.pc_vault),
+            ("creator", &pool.creator),
+        ];
+        for (field, value) in fields {
+            if !value.is_empty() {
+                store.set_if_not_exists(ordinal, format!("{amm}:{field}"), value);
+            }
+        }
+        if pool.instruction_type == "initialize" {
+            store.set_if_not_exists(ordinal, format!("{amm}:open_slot"), &slot);
+            if pool.creator == PUMPFUN_MIGRATION_ACCOUNT {
+                for mint in [&pool.coin_mint, &pool.pc_mint] {
+                    if pumpfun_tokens_store.get_at(ordinal, format!("{mint}:complete_slot")).is_some() {
+                        store.set_if_not_exists(ordinal, format!("{mint}:first_amm"), amm);
+                    }
+                }
+            }
+        }
+        store.set_if_not_exists(ordinal, format!("{amm}:first_seen_slot"), &slot);
+    }
+}
+
+/// Keeps the latest reserves of every Raydium AMM pool, keyed by `{amm}:pc_amount` and `{amm}:coin_amount`.
+#[substreams::handlers::store]
+fn store_raydium_pool_reserves(block_events: BlockEvents, store: StoreSetBigInt) {
+    for (ordinal, block_event) in block_events.events.iter().enumerate() {
+        if let Some(Event::RaydiumAmmPool(pool)) = &block_event.event {
+            if let (Some(pool_pc_amount), Some(pool_coin_amount)) = (pool.pool_pc_amount, pool.pool_coin_amount) {
+                let amm = &pool.amm;
+                store.set(ordinal as u64, format!("{amm}:pc_amount"), &BigInt::from(pool_pc_amount));
+                store.set(ordinal as u64, format!("{amm}:coin_amount"), &BigInt::from(pool_coin_amount));
+            }
+        }
+    }
+}
+
+pub struct RaydiumPools {
+    pub store: StoreGetString,
+    pub reserves: StoreGetBigInt,
+}
+
+impl RaydiumPools {
+    pub fn get_field(&self, amm: &str, field: &str) -> Option<String> {
+        self.store.get_last(format!("{amm}:{field}"))
+    }
+
+    /// Age of the pool at `slot`, counted from its Initialize instruction.
+    pub fn get_age_slots(&self, amm: &str, slot: u64) -> Option<u64> {
+        self.get_field(amm, "open_slot").map(|open_slot| slot - open_slot.parse::<u64>().unwrap())
+    }
+
+    /// Whether `amm` was seen for the first time at `slot`.
+    pub fn is_new(&self, amm: &str, slot: u64) -> bool {
+        self.get_field(amm, "first_seen_slot") == Some(slot.to_string())
+    }
+
+    /// Symbols of the coin and pc mints of `amm`, empty when unknown.
+    pub fn get_symbols(&self, amm: &str, mint_stores: &MintStores) -> (String, String) {
+        let coin_symbol = self.get_field(amm, "coin_mint").and_then(|mint| mint_stores.get_field(&mint, "symbol"));
+        let pc_symbol = self.get_field(amm, "pc_mint").and_then(|mint| mint_stores.get_field(&mint, "symbol"));
+        (coin_symbol.unwrap_or_default(), pc_symbol.unwrap_or_default())
+    }
+
+    /// Sets the pool symbols and age on an event row of `amm`.
+    pub fn set_pool_columns(&self, amm: &str, slot: u64, mint_stores: &MintStores, row: &mut Row) {
+        let (coin_symbol, pc_symbol) = self.get_symbols(amm, mint_stores);
+        row
+            .set("coin_symbol", coin_symbol)
+            .set("pc_symbol", pc_symbol);
+        if let Some(pool_age_slots) = self.get_age_slots(amm, slot) {
+            row.set("pool_age_slots", pool_age_slots);
+        }
+    }
+}
+
+/// Upserts the registry entry of `amm` into the `raydium_pools` table.
+pub fn insert_raydium_pool(amm: &str, slot: u64, pools: &RaydiumPools, mint_stores: &MintStores, tables: &mut Tables) {
+    let (coin_symbol, pc_symbol) = pools.get_symbols(amm, mint_stores);
+    let row = tables.create_row("raydium_pools", amm.to_string())
+        .set("coin_symbol", coin_symbol)
+        .set("pc_symbol", pc_symbol)
+        .set("slot", slot);
+    for field in ["coin_mint", "pc_mint", "lp_mint", "coin_vault", "pc_vault", "creator"] {
+        row.set(field, pools.get_field(amm, field).unwrap_or_default());
+    }
+    for field in ["open_slot", "first_seen_slot"] {
+        if let Some(value) = pools.get_field(amm, field) {
+            row.set(field, value);
+        }
+    }
+}
+
+/// Inserts the state of `amm` at the end of the block into the `raydium_pool_states` table.
+///
+/// The price is the pc amount per coin, and the TVL is in USD, when both sides of the pool have a USD price.
+/// The LP supply is only known for LP mints initialized since the first indexed block.
+pub fn insert_raydium_pool_state(amm: &str, slot: u64, pools: &RaydiumPools, mint_stores: &MintStores, usd_prices: &UsdPrices, tables: &mut Tables) {
+    let pc_amount = pools.reserves.get_last(format!("{amm}:pc_amount"));
+    let coin_amount = pools.reserves.get_last(format!("{amm}:coin_amount"));
+    let (pc_amount, coin_amount) = match (pc_amount, coin_amount) {
+        (Some(pc_amount), Some(coin_amount)) => (pc_amount.to_u64(), coin_amount.to_u64()),
+        _ => return,
+    };
+    let pc_mint = pools.get_field(amm, "pc_mint");
+    let coin_mint = pools.get_field(amm, "coin_mint");
+    let lp_supply = pools.get_field(amm, "lp_mint").and_then(|lp_mint| mint_stores.get_supply(&lp_mint));
+
+    let row = tables.create_row("raydium_pool_states", [("amm", amm.to_string()), ("slot", slot.to_string())])
+        .set("pc_amount", pc_amount)
+        .set("coin_amount", coin_amount);
+    if let Some(lp_supply) = lp_supply {
+        row.set("lp_supply", lp_supply.to_string());
+    }
+    let (pc_mint, coin_mint) = match (pc_mint, coin_mint) {
+        (Some(pc_mint), Some(coin_mint)) => (pc_mint, coin_mint),
+        _ => return,
+    };
+    let pc_decimals = get_decimals(&mint_stores.mints, &pc_mint);
+    let coin_decimals = get_decimals(&mint_stores.mints, &coin_mint);
+    if let (Some(pc_decimals), Some(coin_decimals)) = (pc_decimals, coin_decimals) {
+        if coin_amount > 0 {
+            let price = (pc_amount as f64 / 10f64.powi(pc_decimals as i32)) / (coin_amount as f64 / 10f64.powi(coin_decimals as i32));
+            row.set("price", price.to_string());
+        }
+    }
+    let pc_usd_value = usd_prices.get_usd_value(&pc_mint, pc_amount, pc_decimals);
+    let coin_usd_value = usd_prices.get_usd_value(&coin_mint, coin_amount, coin_decimals);
+    if let (Some(pc_usd_value), Some(coin_usd_value)) = (pc_usd_value, coin_usd_value) {
+        row.set("tvl_usd", (pc_usd_value + coin_usd_value).to_string());
+    }
+}
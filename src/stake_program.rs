@@ -0,0 +1,218 @@
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::Pubkey;
+
+use crate::decoding::{get_account, Reader};
+use crate::instruction::IndexedInstruction;
+
+/// Stake11111111111111111111111111111111111111
+pub const STAKE_PROGRAM_ID: Pubkey = Pubkey([6, 161, 216, 23, 145, 55, 84, 42, 152, 52, 55, 189, 254, 42, 122, 178, 85, 127, 83, 92, 138, 120, 114, 43, 104, 164, 157, 192, 0, 0, 0, 0]);
+
+const INITIALIZE_TAG: u32 = 0;
+const AUTHORIZE_TAG: u32 = 1;
+const DELEGATE_STAKE_TAG: u32 = 2;
+const SPLIT_TAG: u32 = 3;
+const WITHDRAW_TAG: u32 = 4;
+const DEACTIVATE_TAG: u32 = 5;
+const SET_LOCKUP_TAG: u32 = 6;
+const MERGE_TAG: u32 = 7;
+const AUTHORIZE_WITH_SEED_TAG: u32 = 8;
+const INITIALIZE_CHECKED_TAG: u32 = 9;
+const AUTHORIZE_CHECKED_TAG: u32 = 10;
+const AUTHORIZE_CHECKED_WITH_SEED_TAG: u32 = 11;
+const SET_LOCKUP_CHECKED_TAG: u32 = 12;
+
+#[derive(Debug)]
+pub enum StakeProgramEvent {
+    Initialize(InitializeEvent),
+    Authorize(AuthorizeEvent),
+    DelegateStake(DelegateStakeEvent),
+    Split(SplitEvent),
+    Withdraw(WithdrawEvent),
+    Deactivate(DeactivateEvent),
+    SetLockup(SetLockupEvent),
+    Merge(MergeEvent),
+}
+
+/// An initialization of a stake account, checked or not.
+///
+/// The checked instruction takes no lockup, so its lockup is the default one.
+#[derive(Debug)]
+pub struct InitializeEvent {
+    pub instruction_type: &'static str,
+    pub stake_account: String,
+    pub staker: String,
+    pub withdrawer: String,
+    pub lockup_unix_timestamp: i64,
+    pub lockup_epoch: u64,
+    pub lockup_custodian: String,
+}
+
+/// A change of the staker or the withdrawer of a stake account.
+///
+/// For the instructions with a seed, the authority is the base account the current authority derives from.
+#[derive(Debug)]
+pub struct AuthorizeEvent {
+    pub instruction_type: &'static str,
+    pub stake_account: String,
+    pub authority: String,
+    pub new_authority: String,
+    pub authority_type: &'static str,
+}
+
+#[derive(Debug)]
+pub struct DelegateStakeEvent {
+    pub stake_account: String,
+    pub vote_account: String,
+    pub stake_authority: String,
+}
+
+#[derive(Debug)]
+pub struct SplitEvent {
+    pub stake_account: String,
+    pub split_stake_account: String,
+    pub stake_authority: String,
+    pub lamports: u64,
+}
+
+#[derive(Debug)]
+pub struct WithdrawEvent {
+    pub stake_account: String,
+    pub recipient_account: String,
+    pub withdraw_authority: String,
+    pub lamports: u64,
+}
+
+#[derive(Debug)]
+pub struct DeactivateEvent {
+    pub stake_account: String,
+    pub stake_authority: String,
+}
+
+/// A change of the lockup of a stake account, where only the given fields are changed.
+#[derive(Debug)]
+pub struct SetLockupEvent {
+    pub instruction_type: &'static str,
+    pub stake_account: String,
+    pub authority: String,
+    pub unix_timestamp: Option<i64>,
+    pub epoch: Option<u64>,
+    pub custodian: Option<String>,
+}
+
+/// A merge of the source stake account into the destination, which closes the source.
+#[derive(Debug)]
+pub struct MergeEvent {
+    pub destination_stake_account: String,
+    pub source_stake_account: String,
+    pub stake_authority: String,
+}
+
+/// Parses an instruction of the Stake program, whose data is serialized with bincode.
+pub fn parse_instruction(instruction: &IndexedInstruction) -> Result<Option<StakeProgramEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let tag = match reader.read_u32() {
+        Ok(tag) => tag,
+        Err(_) => return Ok(None),
+    };
+    let event = match tag {
+        INITIALIZE_TAG => StakeProgramEvent::Initialize(InitializeEvent {
+            instruction_type: "initialize",
+            stake_account: get_account(instruction, 0)?,
+            staker: reader.read_pubkey()?,
+            withdrawer: reader.read_pubkey()?,
+            lockup_unix_timestamp: reader.read_i64()?,
+            lockup_epoch: reader.read_u64()?,
+            lockup_custodian: reader.read_pubkey()?,
+        }),
+        INITIALIZE_CHECKED_TAG => StakeProgramEvent::Initialize(InitializeEvent {
+            instruction_type: "initialize_checked",
+            stake_account: get_account(instruction, 0)?,
+            staker: get_account(instruction, 2)?,
+            withdrawer: get_account(instruction, 3)?,
+            lockup_unix_timestamp: 0,
+            lockup_epoch: 0,
+            lockup_custodian: Pubkey([0; 32]).to_string(),
+        }),
+        AUTHORIZE_TAG => StakeProgramEvent::Authorize(AuthorizeEvent {
+            instruction_type: "authorize",
+            stake_account: get_account(instruction, 0)?,
+            authority: get_account(instruction, 2)?,
+            new_authority: reader.read_pubkey()?,
+            authority_type: get_authority_type_name(reader.read_u32()?),
+        }),
+        AUTHORIZE_WITH_SEED_TAG => StakeProgramEvent::Authorize(AuthorizeEvent {
+            instruction_type: "authorize_with_seed",
+            stake_account: get_account(instruction, 0)?,
+            authority: get_account(instruction, 1)?,
+            new_authority: reader.read_pubkey()?,
+            authority_type: get_authority_type_name(reader.read_u32()?),
+        }),
+        AUTHORIZE_CHECKED_TAG => StakeProgramEvent::Authorize(AuthorizeEvent {
+            instruction_type: "authorize_checked",
+            stake_account: get_account(instruction, 0)?,
+            authority: get_account(instruction, 2)?,
+            new_authority: get_account(instruction, 3)?,
+            authority_type: get_authority_type_name(reader.read_u32()?),
+        }),
+        AUTHORIZE_CHECKED_WITH_SEED_TAG => StakeProgramEvent::Authorize(AuthorizeEvent {
+            instruction_type: "authorize_checked_with_seed",
+            stake_account: get_account(instruction, 0)?,
+            authority: get_account(instruction, 1)?,
+            new_authority: get_account(instruction, 3)?,
+            authority_type: get_authority_type_name(reader.read_u32()?),
+        }),
+        DELEGATE_STAKE_TAG => StakeProgramEvent::DelegateStake(DelegateStakeEvent {
+            stake_account: get_account(instruction, 0)?,
+            vote_account: get_account(instruction, 1)?,
+            stake_authority: get_account(instruction, 5)?,
+        }),
+        SPLIT_TAG => StakeProgramEvent::Split(SplitEvent {
+            stake_account: get_account(instruction, 0)?,
+            split_stake_account: get_account(instruction, 1)?,
+            stake_authority: get_account(instruction, 2)?,
+            lamports: reader.read_u64()?,
+        }),
+        WITHDRAW_TAG => StakeProgramEvent::Withdraw(WithdrawEvent {
+            stake_account: get_account(instruction, 0)?,
+            recipient_account: get_account(instruction, 1)?,
+            withdraw_authority: get_account(instruction, 4)?,
+            lamports: reader.read_u64()?,
+        }),
+        DEACTIVATE_TAG => StakeProgramEvent::Deactivate(DeactivateEvent {
+            stake_account: get_account(instruction, 0)?,
+            stake_authority: get_account(instruction, 2)?,
+        }),
+        SET_LOCKUP_TAG => StakeProgramEvent::SetLockup(SetLockupEvent {
+            instruction_type: "set_lockup",
+            stake_account: get_account(instruction, 0)?,
+            authority: get_account(instruction, 1)?,
+            unix_timestamp: reader.read_option(|reader| reader.read_i64())?,
+            epoch: reader.read_option(|reader| reader.read_u64())?,
+            custodian: reader.read_option(|reader| reader.read_pubkey())?,
+        }),
+        SET_LOCKUP_CHECKED_TAG => StakeProgramEvent::SetLockup(SetLockupEvent {
+            instruction_type: "set_lockup_checked",
+            stake_account: get_account(instruction, 0)?,
+            authority: get_account(instruction, 1)?,
+            unix_timestamp: reader.read_option(|reader| reader.read_i64())?,
+            epoch: reader.read_option(|reader| reader.read_u64())?,
+            // The new custodian is passed as a signer account instead.
+            custodian: get_account(instruction, 2).ok(),
+        }),
+        MERGE_TAG => StakeProgramEvent::Merge(MergeEvent {
+            destination_stake_account: get_account(instruction, 0)?,
+            source_stake_account: get_account(instruction, 1)?,
+            stake_authority: get_account(instruction, 4)?,
+        }),
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+fn get_authority_type_name(stake_authorize: u32) -> &'static str {
+    match stake_authorize {
+        0 => "staker",
+        _ => "withdrawer",
+    }
+}
@@ -0,0 +1,83 @@
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::spl_token::constants::TOKEN_PROGRAM_ID;
+use substreams_solana_utils::transaction::TransactionContext;
+
+use spl_token_substream::pb::spl_token::spl_token_event;
+
+use crate::decoding::Reader;
+use crate::instruction::IndexedInstruction;
+
+/// TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = Pubkey([6, 221, 246, 225, 238, 117, 143, 222, 24, 66, 93, 188, 228, 108, 205, 218, 182, 26, 252, 77, 131, 185, 13, 39, 254, 189, 249, 40, 216, 161, 139, 252]);
+
+const TRANSFER_CHECKED_TAG: u8 = 12;
+
+#[derive(Debug, Clone)]
+pub struct TokenTransfer {
+    pub source: String,
+    pub destination: String,
+    pub mint: String,
+    pub authority: String,
+    pub amount: u64,
+}
+
+/// The SPL Token and Token-2022 transfers made by the direct inner instructions of a DEX instruction.
+#[derive(Debug, Default)]
+pub struct TokenTransfers(pub Vec<TokenTransfer>);
+
+impl TokenTransfers {
+    /// Collects the transfers of `instruction`.
+    ///
+    /// Transfers which `spl_token_substream` can't resolve, e.g. between Token-2022 accounts it doesn't track,
+    /// fall back to decoding `TransferChecked` from the instruction data.
+    pub fn new(instruction: &IndexedInstruction, context: &TransactionContext) -> Self {
+        let mut transfers = Vec::new();
+        for inner_instruction in instruction.inner_instructions().iter() {
+            let program_id = inner_instruction.program_id();
+            if program_id != TOKEN_PROGRAM_ID && program_id != TOKEN_2022_PROGRAM_ID {
+                continue;
+            }
+            if let Ok(Some(spl_token_event::Event::Transfer(transfer))) = spl_token_substream::parse_instruction(&inner_instruction.instruction, context) {
+                if let (Some(source), Some(destination)) = (transfer.source, transfer.destination) {
+                    transfers.push(TokenTransfer {
+                        source: source.address,
+                        destination: destination.address,
+                        mint: source.mint,
+                        authority: transfer.authority,
+                        amount: transfer.amount,
+                    });
+                    continue;
+                }
+            }
+            if inner_instruction.data().first() == Some(&TRANSFER_CHECKED_TAG) {
+                let accounts = inner_instruction.accounts();
+                let amount = Reader::new(&inner_instruction.data()[1..]).read_u64();
+                if let (Ok(amount), true) = (amount, accounts.len() >= 4) {
+                    transfers.push(TokenTransfer {
+                        source: accounts[0].to_string(),
+                        mint: accounts[1].to_string(),
+                        destination: accounts[2].to_string(),
+                        authority: accounts[3].to_string(),
+                        amount,
+                    });
+                }
+            }
+        }
+        TokenTransfers(transfers)
+    }
+
+    /// Total amount transferred into `account`.
+    pub fn amount_to(&self, account: &str) -> u64 {
+        self.0.iter().filter(|x| x.destination == account).map(|x| x.amount).sum()
+    }
+
+    /// Total amount transferred out of `account`.
+    pub fn amount_from(&self, account: &str) -> u64 {
+        self.0.iter().filter(|x| x.source == account).map(|x| x.amount).sum()
+    }
+
+    /// Mint of the transfers involving `account`.
+    pub fn get_mint(&self, account: &str) -> Option<String> {
+        self.0.iter().find(|x| x.source == account || x.destination == account).map(|x| x.mint.clone())
+    }
+}
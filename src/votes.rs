@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Error};
+
+use substreams_database_change::tables::Tables;
+use substreams_solana::pb::sf::solana::r#type::v1::{CompiledInstruction, ConfirmedTransaction};
+
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::transaction::get_signature;
+
+use crate::decoding::{to_array_value, Reader};
+
+/// Vote111111111111111111111111111111111111111
+pub const VOTE_PROGRAM_ID: Pubkey = Pubkey([7, 97, 72, 29, 53, 116, 116, 187, 124, 77, 118, 36, 235, 211, 189, 179, 216, 53, 94, 115, 209, 16, 67, 252, 13, 163, 83, 128, 0, 0, 0, 0]);
+
+const VOTE_TAG: u32 = 2;
+const VOTE_SWITCH_TAG: u32 = 6;
+const UPDATE_VOTE_STATE_TAG: u32 = 8;
+const UPDATE_VOTE_STATE_SWITCH_TAG: u32 = 9;
+const COMPACT_UPDATE_VOTE_STATE_TAG: u32 = 12;
+const COMPACT_UPDATE_VOTE_STATE_SWITCH_TAG: u32 = 13;
+const TOWER_SYNC_TAG: u32 = 14;
+const TOWER_SYNC_SWITCH_TAG: u32 = 15;
+
+/// How vote transactions are indexed, from the params of `block_database_changes`.
+///
+/// Vote transactions are always kept out of the other tables. With `skip` they are only counted on the
+/// blocks row, with `count` they are also counted per vote account in `vote_counts`, and with `events`
+/// every vote is inserted into `vote_events`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VoteMode {
+    Skip,
+    Count,
+    Events,
+}
+
+impl VoteMode {
+    pub fn from_params(params: &str) -> Result<Self, Error> {
+        match params.trim() {
+            "" | "skip" => Ok(VoteMode::Skip),
+            "count" => Ok(VoteMode::Count),
+            "events" => Ok(VoteMode::Events),
+            mode => Err(anyhow!("Unknown vote mode {}, expected skip, count or events.", mode)),
+        }
+    }
+}
+
+/// A vote of a validator, with the slots of its tower for the instructions which sync it.
+#[derive(Debug)]
+pub struct VoteEvent {
+    pub instruction_type: &'static str,
+    pub vote_account: String,
+    pub vote_authority: String,
+    pub voted_slots: Vec<u64>,
+    pub root_slot: Option<u64>,
+    pub timestamp: Option<i64>,
+}
+
+/// The vote transactions of a block, counted per vote account.
+#[derive(Debug, Default)]
+pub struct VoteCounts {
+    pub transactions: u32,
+    pub by_vote_account: BTreeMap<String, u32>,
+}
+
+/// Whether every instruction of `transaction` casts a vote.
+///
+/// This only looks at the compiled instructions, so that vote transactions can be handled without
+/// building their context. Votes don't invoke other programs nor use lookup tables.
+pub fn is_vote_transaction(transaction: &ConfirmedTransaction) -> bool {
+    let message = match transaction.transaction.as_ref().and_then(|x| x.message.as_ref()) {
+        Some(message) => message,
+        None => return false,
+    };
+    !message.instructions.is_empty() && message.instructions.iter().all(|instruction| {
+        message.account_keys.get(instruction.program_id_index as usize).map(|x| x.as_slice()) == Some(&VOTE_PROGRAM_ID.0[..])
+            && Reader::new(&instruction.data).read_u32().map_or(false, is_vote_tag)
+    })
+}
+
+fn is_vote_tag(tag: u32) -> bool {
+    matches!(
+        tag,
+        VOTE_TAG | VOTE_SWITCH_TAG | UPDATE_VOTE_STATE_TAG | UPDATE_VOTE_STATE_SWITCH_TAG
+        | COMPACT_UPDATE_VOTE_STATE_TAG | COMPACT_UPDATE_VOTE_STATE_SWITCH_TAG | TOWER_SYNC_TAG | TOWER_SYNC_SWITCH_TAG
+    )
+}
+
+/// Counts or inserts the votes of a transaction for which `is_vote_transaction` holds.
+pub fn parse_vote_transaction(
+    transaction: &ConfirmedTransaction,
+    transaction_index: u32,
+    slot: u64,
+    blockhash: &String,
+    vote_mode: VoteMode,
+    vote_counts: &mut VoteCounts,
+    tables: &mut Tables,
+) -> Result<(), Error> {
+    vote_counts.transactions += 1;
+    if vote_mode == VoteMode::Skip {
+        return Ok(());
+    }
+
+    let message = transaction.transaction.as_ref().unwrap().message.as_ref().unwrap();
+    if vote_mode == VoteMode::Count {
+        for instruction in message.instructions.iter() {
+            let vote_account = get_account(instruction, &message.account_keys, 0)?;
+            *vote_counts.by_vote_account.entry(vote_account).or_default() += 1;
+        }
+        return Ok(());
+    }
+
+    let signature = get_signature(transaction);
+    for (index, instruction) in message.instructions.iter().enumerate() {
+        let vote = parse_instruction(instruction, &message.account_keys)?;
+        let row = tables.create_row("vote_events", [("slot", slot.to_string()), ("transaction_index", transaction_index.to_string()), ("instruction_index", index.to_string())])
+            .set("partial_signature", &signature[0..4])
+            .set("partial_blockhash", &blockhash[0..4])
+            .set("type", vote.instruction_type)
+            .set("vote_account", vote.vote_account)
+            .set("vote_authority", vote.vote_authority)
+            .set("voted_slots", to_array_value(&vote.voted_slots));
+        if let Some(last_voted_slot) = vote.voted_slots.last() {
+            row.set("last_voted_slot", *last_voted_slot);
+        }
+        if let Some(root_slot) = vote.root_slot {
+            row.set("root_slot", root_slot);
+        }
+        if let Some(timestamp) = vote.timestamp {
+            row.set("timestamp", timestamp);
+        }
+    }
+    Ok(())
+}
+
+pub fn insert_vote_counts(vote_counts: &VoteCounts, slot: u64, tables: &mut Tables) {
+    for (vote_account, votes) in vote_counts.by_vote_account.iter() {
+        tables.create_row("vote_counts", [("slot", slot.to_string()), ("vote_account", vote_account.clone())])
+            .set("votes", *votes);
+    }
+}
+
+/// Parses a vote instruction, whose data is serialized with bincode.
+pub fn parse_instruction(instruction: &CompiledInstruction, account_keys: &[Vec<u8>]) -> Result<VoteEvent, Error> {
+    let mut reader = Reader::new(&instruction.data);
+    let tag = reader.read_u32()?;
+    let (instruction_type, vote_authority_index) = match tag {
+        VOTE_TAG => ("vote", 3),
+        VOTE_SWITCH_TAG => ("vote_switch", 3),
+        UPDATE_VOTE_STATE_TAG => ("update_vote_state", 1),
+        UPDATE_VOTE_STATE_SWITCH_TAG => ("update_vote_state_switch", 1),
+        COMPACT_UPDATE_VOTE_STATE_TAG => ("compact_update_vote_state", 1),
+        COMPACT_UPDATE_VOTE_STATE_SWITCH_TAG => ("compact_update_vote_state_switch", 1),
+        TOWER_SYNC_TAG => ("tower_sync", 1),
+        TOWER_SYNC_SWITCH_TAG => ("tower_sync_switch", 1),
+        _ => return Err(anyhow!("Vote instruction {} doesn't cast a vote.", tag)),
+    };
+    let (voted_slots, root_slot) = match tag {
+        VOTE_TAG | VOTE_SWITCH_TAG => {
+            let length = reader.read_u64()?;
+            let slots = (0..length).map(|_| reader.read_u64()).collect::<Result<Vec<_>, _>>()?;
+            (slots, None)
+        },
+        UPDATE_VOTE_STATE_TAG | UPDATE_VOTE_STATE_SWITCH_TAG => {
+            let length = reader.read_u64()?;
+            let slots = (0..length)
+                .map(|_| {
+                    let slot = reader.read_u64()?;
+                    let _confirmation_count = reader.read_u32()?;
+                    Ok(slot)
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            (slots, reader.read_option(|reader| reader.read_u64())?)
+        },
+        _ => {
+            // The compact encoding stores the root, with `u64::MAX` for none, and the lockouts as offsets from it.
+            let root_slot = Some(reader.read_u64()?).filter(|x| *x != u64::MAX);
+            let length = reader.read_varint()?;
+            let mut slot = root_slot.unwrap_or(0);
+            let slots = (0..length)
+                .map(|_| {
+                    slot += reader.read_varint()?;
+                    let _confirmation_count = reader.read_u8()?;
+                    Ok(slot)
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            (slots, root_slot)
+        },
+    };
+    // Skips the hash of the last voted slot.
+    reader.read_bytes(32)?;
+    Ok(VoteEvent {
+        instruction_type,
+        vote_account: get_account(instruction, account_keys, 0)?,
+        vote_authority: get_account(instruction, account_keys, vote_authority_index)?,
+        voted_slots,
+        root_slot,
+        timestamp: reader.read_option(|reader| reader.read_i64())?,
+    })
+}
+
+fn get_account(instruction: &CompiledInstruction, account_keys: &[Vec<u8>], index: usize) -> Result<String, Error> {
+    instruction.accounts.get(index)
+        .and_then(|x| account_keys.get(*x as usize))
+        .map(|x| bs58::encode(x).into_string())
+        .ok_or(anyhow!("Missing account {} of vote instruction.", index))
+}
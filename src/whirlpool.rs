@@ -0,0 +1,412 @@
+use anyhow::Error;
+
+use substreams_solana_utils::pubkey::Pubkey;
+use substreams_solana_utils::transaction::TransactionContext;
+
+use crate::decoding::{decode_event, find_events, get_account, get_log_events, Reader};
+use crate::instruction::IndexedInstruction;
+use crate::token_transfers::TokenTransfers;
+
+/// whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey = Pubkey([14, 3, 104, 95, 142, 144, 144, 83, 228, 88, 18, 28, 102, 245, 167, 106, 237, 199, 112, 106, 161, 28, 130, 248, 170, 149, 42, 143, 43, 120, 121, 169]);
+
+const INITIALIZE_POOL_DISCRIMINATOR: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+const INITIALIZE_POOL_V2_DISCRIMINATOR: [u8; 8] = [207, 45, 87, 242, 27, 63, 204, 67];
+const OPEN_POSITION_DISCRIMINATOR: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+const OPEN_POSITION_WITH_METADATA_DISCRIMINATOR: [u8; 8] = [242, 29, 134, 48, 58, 110, 14, 60];
+const OPEN_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR: [u8; 8] = [212, 47, 95, 92, 114, 102, 131, 250];
+const CLOSE_POSITION_DISCRIMINATOR: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+const CLOSE_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR: [u8; 8] = [1, 182, 135, 59, 155, 25, 99, 223];
+const INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+const INCREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] = [133, 29, 89, 223, 69, 238, 176, 10];
+const DECREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
+const DECREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] = [58, 127, 188, 62, 79, 82, 196, 96];
+const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const SWAP_V2_DISCRIMINATOR: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+const TWO_HOP_SWAP_DISCRIMINATOR: [u8; 8] = [195, 96, 237, 108, 68, 162, 219, 230];
+const TWO_HOP_SWAP_V2_DISCRIMINATOR: [u8; 8] = [186, 143, 209, 29, 254, 2, 194, 117];
+const COLLECT_FEES_DISCRIMINATOR: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+const COLLECT_FEES_V2_DISCRIMINATOR: [u8; 8] = [207, 117, 95, 191, 229, 180, 226, 15];
+const COLLECT_REWARD_DISCRIMINATOR: [u8; 8] = [70, 5, 132, 87, 86, 235, 177, 34];
+const COLLECT_REWARD_V2_DISCRIMINATOR: [u8; 8] = [177, 107, 37, 180, 160, 19, 49, 209];
+
+const TRADED_EVENT_DISCRIMINATOR: [u8; 8] = [225, 202, 73, 175, 147, 43, 160, 150];
+
+#[derive(Debug)]
+pub enum WhirlpoolEvent {
+    InitializePool(InitializePoolEvent),
+    OpenPosition(OpenPositionEvent),
+    ClosePosition(ClosePositionEvent),
+    IncreaseLiquidity(LiquidityEvent),
+    DecreaseLiquidity(LiquidityEvent),
+    Swap(SwapEvent),
+    TwoHopSwap(SwapEvent, SwapEvent),
+    Collect(CollectEvent),
+}
+
+#[derive(Debug)]
+pub struct InitializePoolEvent {
+    pub whirlpool: String,
+    pub whirlpools_config: String,
+    pub funder: String,
+    pub token_mint_a: String,
+    pub token_mint_b: String,
+    pub token_vault_a: String,
+    pub token_vault_b: String,
+    pub tick_spacing: u16,
+    pub initial_sqrt_price: u128,
+}
+
+#[derive(Debug)]
+pub struct OpenPositionEvent {
+    pub whirlpool: String,
+    pub owner: String,
+    pub position: String,
+    pub position_mint: String,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+}
+
+#[derive(Debug)]
+pub struct ClosePositionEvent {
+    pub position_authority: String,
+    pub receiver: String,
+    pub position: String,
+    pub position_mint: String,
+}
+
+/// An increase or a decrease of the liquidity of a position.
+///
+/// The token limits are maximums for increases, and minimums for decreases.
+#[derive(Debug)]
+pub struct LiquidityEvent {
+    pub whirlpool: String,
+    pub position_authority: String,
+    pub position: String,
+    pub liquidity: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub token_a_limit: u64,
+    pub token_b_limit: u64,
+    pub token_mint_a: Option<String>,
+    pub token_mint_b: Option<String>,
+}
+
+/// A swap through one whirlpool, with the sqrt prices around it when the program logged its `Traded` event.
+///
+/// The amount threshold and the price limit are the ones of the whole instruction.
+#[derive(Debug)]
+pub struct SwapEvent {
+    pub whirlpool: String,
+    pub user: String,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub a_to_b: bool,
+    pub amount_specified_is_input: bool,
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit: u128,
+    pub pre_sqrt_price: Option<u128>,
+    pub post_sqrt_price: Option<u128>,
+}
+
+/// A collection of fees, or of the reward `reward_index` into the `a` columns.
+#[derive(Debug)]
+pub struct CollectEvent {
+    pub collect_type: &'static str,
+    pub whirlpool: String,
+    pub position_authority: String,
+    pub position: String,
+    pub reward_index: Option<u8>,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub token_mint_a: Option<String>,
+    pub token_mint_b: Option<String>,
+}
+
+pub fn parse_instruction(instruction: &IndexedInstruction, context: &TransactionContext) -> Result<Option<WhirlpoolEvent>, Error> {
+    let mut reader = Reader::new(instruction.data());
+    let discriminator = match reader.read_discriminator() {
+        Ok(discriminator) => discriminator,
+        Err(_) => return Ok(None),
+    };
+    let event = match discriminator {
+        INITIALIZE_POOL_DISCRIMINATOR | INITIALIZE_POOL_V2_DISCRIMINATOR => {
+            // The v2 instruction takes the token badges of both mints, and no bumps.
+            let offset = match discriminator {
+                INITIALIZE_POOL_DISCRIMINATOR => {
+                    let _whirlpool_bump = reader.read_u8()?;
+                    0
+                },
+                _ => 2,
+            };
+            WhirlpoolEvent::InitializePool(InitializePoolEvent {
+                whirlpools_config: get_account(instruction, 0)?,
+                token_mint_a: get_account(instruction, 1)?,
+                token_mint_b: get_account(instruction, 2)?,
+                funder: get_account(instruction, 3 + offset)?,
+                whirlpool: get_account(instruction, 4 + offset)?,
+                token_vault_a: get_account(instruction, 5 + offset)?,
+                token_vault_b: get_account(instruction, 6 + offset)?,
+                tick_spacing: reader.read_u16()?,
+                initial_sqrt_price: reader.read_u128()?,
+            })
+        },
+        OPEN_POSITION_DISCRIMINATOR | OPEN_POSITION_WITH_METADATA_DISCRIMINATOR | OPEN_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR => {
+            let whirlpool_index = match discriminator {
+                OPEN_POSITION_DISCRIMINATOR => {
+                    let _position_bump = reader.read_u8()?;
+                    5
+                },
+                OPEN_POSITION_WITH_METADATA_DISCRIMINATOR => {
+                    let _position_bump = reader.read_u8()?;
+                    let _metadata_bump = reader.read_u8()?;
+                    6
+                },
+                _ => 5,
+            };
+            WhirlpoolEvent::OpenPosition(OpenPositionEvent {
+                owner: get_account(instruction, 1)?,
+                position: get_account(instruction, 2)?,
+                position_mint: get_account(instruction, 3)?,
+                whirlpool: get_account(instruction, whirlpool_index)?,
+                tick_lower_index: reader.read_i32()?,
+                tick_upper_index: reader.read_i32()?,
+            })
+        },
+        CLOSE_POSITION_DISCRIMINATOR | CLOSE_POSITION_WITH_TOKEN_EXTENSIONS_DISCRIMINATOR => WhirlpoolEvent::ClosePosition(ClosePositionEvent {
+            position_authority: get_account(instruction, 0)?,
+            receiver: get_account(instruction, 1)?,
+            position: get_account(instruction, 2)?,
+            position_mint: get_account(instruction, 3)?,
+        }),
+        INCREASE_LIQUIDITY_DISCRIMINATOR | INCREASE_LIQUIDITY_V2_DISCRIMINATOR | DECREASE_LIQUIDITY_DISCRIMINATOR | DECREASE_LIQUIDITY_V2_DISCRIMINATOR => {
+            let is_v2 = discriminator == INCREASE_LIQUIDITY_V2_DISCRIMINATOR || discriminator == DECREASE_LIQUIDITY_V2_DISCRIMINATOR;
+            let is_increase = discriminator == INCREASE_LIQUIDITY_DISCRIMINATOR || discriminator == INCREASE_LIQUIDITY_V2_DISCRIMINATOR;
+            // The v2 instructions take a second token program and the memo program.
+            let offset = if is_v2 { 2 } else { 0 };
+            let transfers = TokenTransfers::new(instruction, context);
+            let (token_vault_a, token_vault_b) = match is_v2 {
+                true => (get_account(instruction, 11)?, get_account(instruction, 12)?),
+                false => (get_account(instruction, 7)?, get_account(instruction, 8)?),
+            };
+            let (token_a_amount, token_b_amount) = match is_increase {
+                true => (transfers.amount_to(&token_vault_a), transfers.amount_to(&token_vault_b)),
+                false => (transfers.amount_from(&token_vault_a), transfers.amount_from(&token_vault_b)),
+            };
+            let event = LiquidityEvent {
+                whirlpool: get_account(instruction, 0)?,
+                position_authority: get_account(instruction, 2 + offset)?,
+                position: get_account(instruction, 3 + offset)?,
+                liquidity: reader.read_u128()?,
+                token_a_limit: reader.read_u64()?,
+                token_b_limit: reader.read_u64()?,
+                token_a_amount,
+                token_b_amount,
+                token_mint_a: transfers.get_mint(&token_vault_a),
+                token_mint_b: transfers.get_mint(&token_vault_b),
+            };
+            match is_increase {
+                true => WhirlpoolEvent::IncreaseLiquidity(event),
+                false => WhirlpoolEvent::DecreaseLiquidity(event),
+            }
+        },
+        SWAP_DISCRIMINATOR | SWAP_V2_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let (user, whirlpool, token_vault_a, token_vault_b) = match discriminator {
+                SWAP_DISCRIMINATOR => (get_account(instruction, 1)?, get_account(instruction, 2)?, get_account(instruction, 4)?, get_account(instruction, 6)?),
+                _ => (get_account(instruction, 3)?, get_account(instruction, 4)?, get_account(instruction, 8)?, get_account(instruction, 10)?),
+            };
+            let (other_amount_threshold, sqrt_price_limit, amount_specified_is_input, a_to_b) = read_swap_args(&mut reader)?;
+            let (pre_sqrt_price, post_sqrt_price) = get_traded_sqrt_prices(instruction, 0);
+            let (vault_in, vault_out) = if a_to_b { (token_vault_a, token_vault_b) } else { (token_vault_b, token_vault_a) };
+            WhirlpoolEvent::Swap(SwapEvent {
+                whirlpool,
+                user,
+                mint_in: transfers.get_mint(&vault_in).unwrap_or_default(),
+                mint_out: transfers.get_mint(&vault_out).unwrap_or_default(),
+                amount_in: transfers.amount_to(&vault_in),
+                amount_out: transfers.amount_from(&vault_out),
+                a_to_b,
+                amount_specified_is_input,
+                other_amount_threshold,
+                sqrt_price_limit,
+                pre_sqrt_price,
+                post_sqrt_price,
+            })
+        },
+        TWO_HOP_SWAP_DISCRIMINATOR | TWO_HOP_SWAP_V2_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let _amount = reader.read_u64()?;
+            let other_amount_threshold = reader.read_u64()?;
+            let amount_specified_is_input = reader.read_bool()?;
+            let a_to_b_one = reader.read_bool()?;
+            let a_to_b_two = reader.read_bool()?;
+            let sqrt_price_limit_one = reader.read_u128()?;
+            let sqrt_price_limit_two = reader.read_u128()?;
+            let (user, whirlpool_one, whirlpool_two, vault_one_in, vault_one_out, vault_two_in, vault_two_out) = match discriminator {
+                TWO_HOP_SWAP_DISCRIMINATOR => {
+                    let (vault_one_a, vault_one_b) = (get_account(instruction, 5)?, get_account(instruction, 7)?);
+                    let (vault_two_a, vault_two_b) = (get_account(instruction, 9)?, get_account(instruction, 11)?);
+                    let (vault_one_in, vault_one_out) = if a_to_b_one { (vault_one_a, vault_one_b) } else { (vault_one_b, vault_one_a) };
+                    let (vault_two_in, vault_two_out) = if a_to_b_two { (vault_two_a, vault_two_b) } else { (vault_two_b, vault_two_a) };
+                    (get_account(instruction, 1)?, get_account(instruction, 2)?, get_account(instruction, 3)?, vault_one_in, vault_one_out, vault_two_in, vault_two_out)
+                },
+                _ => (
+                    get_account(instruction, 14)?,
+                    get_account(instruction, 0)?,
+                    get_account(instruction, 1)?,
+                    get_account(instruction, 9)?,
+                    get_account(instruction, 10)?,
+                    get_account(instruction, 11)?,
+                    get_account(instruction, 12)?,
+                ),
+            };
+            let (pre_sqrt_price_one, post_sqrt_price_one) = get_traded_sqrt_prices(instruction, 0);
+            let (pre_sqrt_price_two, post_sqrt_price_two) = get_traded_sqrt_prices(instruction, 1);
+            let swap_one = SwapEvent {
+                whirlpool: whirlpool_one,
+                user: user.clone(),
+                mint_in: transfers.get_mint(&vault_one_in).unwrap_or_default(),
+                mint_out: transfers.get_mint(&vault_one_out).unwrap_or_default(),
+                amount_in: transfers.amount_to(&vault_one_in),
+                amount_out: transfers.amount_from(&vault_one_out),
+                a_to_b: a_to_b_one,
+                amount_specified_is_input,
+                other_amount_threshold,
+                sqrt_price_limit: sqrt_price_limit_one,
+                pre_sqrt_price: pre_sqrt_price_one,
+                post_sqrt_price: post_sqrt_price_one,
+            };
+            let swap_two = SwapEvent {
+                whirlpool: whirlpool_two,
+                user,
+                mint_in: transfers.get_mint(&vault_two_in).unwrap_or_default(),
+                mint_out: transfers.get_mint(&vault_two_out).unwrap_or_default(),
+                amount_in: transfers.amount_to(&vault_two_in),
+                amount_out: transfers.amount_from(&vault_two_out),
+                a_to_b: a_to_b_two,
+                amount_specified_is_input,
+                other_amount_threshold,
+                sqrt_price_limit: sqrt_price_limit_two,
+                pre_sqrt_price: pre_sqrt_price_two,
+                post_sqrt_price: post_sqrt_price_two,
+            };
+            WhirlpoolEvent::TwoHopSwap(swap_one, swap_two)
+        },
+        COLLECT_FEES_DISCRIMINATOR | COLLECT_FEES_V2_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let (token_vault_a, token_vault_b) = match discriminator {
+                COLLECT_FEES_DISCRIMINATOR => (get_account(instruction, 5)?, get_account(instruction, 7)?),
+                _ => (get_account(instruction, 7)?, get_account(instruction, 9)?),
+            };
+            WhirlpoolEvent::Collect(CollectEvent {
+                collect_type: "collect_fees",
+                whirlpool: get_account(instruction, 0)?,
+                position_authority: get_account(instruction, 1)?,
+                position: get_account(instruction, 2)?,
+                reward_index: None,
+                token_a_amount: transfers.amount_from(&token_vault_a),
+                token_b_amount: transfers.amount_from(&token_vault_b),
+                token_mint_a: transfers.get_mint(&token_vault_a),
+                token_mint_b: transfers.get_mint(&token_vault_b),
+            })
+        },
+        COLLECT_REWARD_DISCRIMINATOR | COLLECT_REWARD_V2_DISCRIMINATOR => {
+            let transfers = TokenTransfers::new(instruction, context);
+            let reward_vault = match discriminator {
+                COLLECT_REWARD_DISCRIMINATOR => get_account(instruction, 5)?,
+                _ => get_account(instruction, 6)?,
+            };
+            WhirlpoolEvent::Collect(CollectEvent {
+                collect_type: "collect_reward",
+                whirlpool: get_account(instruction, 0)?,
+                position_authority: get_account(instruction, 1)?,
+                position: get_account(instruction, 2)?,
+                reward_index: Some(reader.read_u8()?),
+                token_a_amount: transfers.amount_from(&reward_vault),
+                token_b_amount: 0,
+                token_mint_a: transfers.get_mint(&reward_vault),
+                token_mint_b: None,
+            })
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+/// Reads the amount threshold, the sqrt price limit and the directions of the amount and of the swap from the arguments of `swap` and `swap_v2`.
+fn read_swap_args(reader: &mut Reader) -> Result<(u64, u128, bool, bool), Error> {
+    let _amount = reader.read_u64()?;
+    let other_amount_threshold = reader.read_u64()?;
+    let sqrt_price_limit = reader.read_u128()?;
+    let amount_specified_is_input = reader.read_bool()?;
+    let a_to_b = reader.read_bool()?;
+    Ok((other_amount_threshold, sqrt_price_limit, amount_specified_is_input, a_to_b))
+}
+
+/// Sqrt prices before and after the `hop`-th swap of `instruction`, from its `Traded` events.
+///
+/// Older versions of the program don't log these events.
+fn get_traded_sqrt_prices(instruction: &IndexedInstruction, hop: usize) -> (Option<u128>, Option<u128>) {
+    let events = get_log_events(instruction);
+    match find_events(&events, TRADED_EVENT_DISCRIMINATOR).get(hop).and_then(|data| decode_event(instruction, data, read_traded_event)) {
+        Some((pre_sqrt_price, post_sqrt_price)) => (Some(pre_sqrt_price), Some(post_sqrt_price)),
+        None => (None, None),
+    }
+}
+
+/// Reads the sqrt prices before and after a swap from its `Traded` event.
+fn read_traded_event(reader: &mut Reader) -> Result<(u128, u128), Error> {
+    // Skips the whirlpool and the direction.
+    reader.read_bytes(32 + 1)?;
+    Ok((reader.read_u128()?, reader.read_u128()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A swap of 1 SOL for USDC, encoded by hand after the layouts of the program's IDL.
+    fn swap_instruction_data() -> Vec<u8> {
+        let mut data = SWAP_DISCRIMINATOR.to_vec();
+        data.extend(1_000_000_000u64.to_le_bytes());
+        data.extend(142_318_005u64.to_le_bytes());
+        data.extend(4_295_048_016u128.to_le_bytes());
+        data.extend([1, 1]);
+        data
+    }
+
+    fn traded_event_data() -> Vec<u8> {
+        let mut data = [7u8; 32].to_vec();
+        data.push(1);
+        data.extend(6_979_633_418_155_317_248u128.to_le_bytes());
+        data.extend(6_975_227_150_624_551_936u128.to_le_bytes());
+        for amount in [1_000_000_000u64, 143_029_151, 0, 0, 400_000, 52_000] {
+            data.extend(amount.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_read_swap_args() {
+        let data = swap_instruction_data();
+        let mut reader = Reader::new(&data);
+        assert_eq!(reader.read_discriminator().unwrap(), SWAP_DISCRIMINATOR);
+        assert_eq!(read_swap_args(&mut reader).unwrap(), (142_318_005, 4_295_048_016, true, true));
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_read_traded_event() {
+        let data = traded_event_data();
+        assert_eq!(read_traded_event(&mut Reader::new(&data)).unwrap(), (6_979_633_418_155_317_248, 6_975_227_150_624_551_936));
+    }
+
+    #[test]
+    fn test_read_truncated_traded_event() {
+        let data = traded_event_data();
+        assert!(read_traded_event(&mut Reader::new(&data[..32 + 1 + 16])).is_err());
+    }
+}